@@ -0,0 +1,394 @@
+//! This module implements the batch JSON-RPC objects.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    error::Error,
+    id::Id,
+    notification::Notification,
+    request::Request,
+    response::{Response, ResponseError},
+};
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(untagged)]
+/// A single entry of a JSON-RPC Batch, which is either a Request or a Notification.
+pub enum BatchEntry {
+    Request(Request),
+    Notification(Notification),
+}
+
+/// Turns the handler `result` for a batch entry into the Response to include in the batch.
+///
+/// Notifications never get a response, not even when the handler errored, so this returns
+/// None for them. If the success value fails to serialize, an internal error is returned.
+pub fn respond_in_batch<T: serde::Serialize>(
+    entry: &BatchEntry,
+    result: Result<T, ResponseError>,
+) -> Option<Response> {
+    let BatchEntry::Request(req) = entry else {
+        return None;
+    };
+    let err = match result.map(serde_json::to_value) {
+        Ok(Ok(value)) => return Some(Response::builder().id(req).success().result(value).build()),
+        Ok(Err(e)) => ResponseError {
+            code: -32603,
+            message: "Internal error".to_string(),
+            data: Some(e.to_string().into()),
+        },
+        Err(e) => e,
+    };
+    Some(
+        Response::builder()
+            .id(req)
+            .error()
+            .with(err.code, &err.message, err.data)
+            .build(),
+    )
+}
+
+/// A parsed incoming message, which is either a single entry or a batch of them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Incoming {
+    Single(BatchEntry),
+    Batch(Batch),
+}
+
+/// Parses either a single Request/Notification or a Batch, picking the one to parse from the
+/// first non-whitespace character (`[` for a batch) instead of trying both.
+///
+/// An empty batch `[]` is rejected, as the spec requires it to be answered with an Invalid
+/// Request error.
+pub fn parse_any(s: &str) -> Result<Incoming, Error> {
+    Incoming::try_from(s.as_bytes())
+}
+
+impl TryFrom<&[u8]> for Incoming {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value)?;
+        match value.iter().find(|b| !b.is_ascii_whitespace()) {
+            Some(b'[') => Batch::try_from(value).map(Incoming::Batch),
+            _ => serde_json::from_slice(value)
+                .map(Incoming::Single)
+                .map_err(|e| e.into()),
+        }
+    }
+}
+
+pub mod builder;
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(try_from = "Vec<BatchEntry>")]
+/// The JSON-RPC Batch, an array of Request and Notification objects.
+///
+/// An empty array is an Invalid Request per the spec, so it fails to deserialize.
+pub struct Batch(pub Vec<BatchEntry>);
+
+impl TryFrom<Vec<BatchEntry>> for Batch {
+    type Error = Error;
+
+    fn try_from(value: Vec<BatchEntry>) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(Error::InvalidType(
+                "batch must contain at least one entry".to_string(),
+            ));
+        }
+        Ok(Batch(value))
+    }
+}
+
+impl Batch {
+    pub fn builder() -> builder::Builder {
+        builder::Builder::new()
+    }
+
+    pub fn push(&mut self, e: BatchEntry) {
+        self.0.push(e);
+    }
+}
+
+impl Deref for Batch {
+    type Target = Vec<BatchEntry>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Batch {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl TryFrom<&str> for Batch {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        serde_json::from_str(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<&[u8]> for Batch {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<Batch> for String {
+    type Error = Error;
+
+    fn try_from(value: Batch) -> Result<Self, Self::Error> {
+        serde_json::to_string(&value).map_err(|e| e.into())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+/// The JSON-RPC Batch Response, an array of Response objects.
+pub struct BatchResponse(pub Vec<Response>);
+
+impl BatchResponse {
+    /// Aligns the responses to the order of the `requests` they answer, correlating by id.
+    ///
+    /// The spec allows a server to answer a batch in any order, so this returns one entry per
+    /// request, holding `None` when no response was received for it. Each response is used at
+    /// most once, so duplicate ids are matched up in order. Requests with a null id are never
+    /// matched, as a null id response can't be attributed to a specific request.
+    pub fn sorted_by(self, requests: &[Request]) -> Vec<Option<Response>> {
+        let mut responses: Vec<Option<Response>> = self.0.into_iter().map(Some).collect();
+        requests
+            .iter()
+            .map(|req| {
+                if req.id == Id::Null {
+                    return None;
+                }
+                responses
+                    .iter_mut()
+                    .find(|rsp| rsp.as_ref().is_some_and(|rsp| rsp.id == req.id))
+                    .and_then(Option::take)
+            })
+            .collect()
+    }
+}
+
+impl Deref for BatchResponse {
+    type Target = Vec<Response>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for BatchResponse {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl TryFrom<&str> for BatchResponse {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        serde_json::from_str(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<&[u8]> for BatchResponse {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<BatchResponse> for String {
+    type Error = Error;
+
+    fn try_from(value: BatchResponse) -> Result<Self, Self::Error> {
+        serde_json::to_string(&value).map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::Status;
+
+    #[test]
+    fn deserialize_batch_entries() {
+        let req = r#"[
+            {"jsonrpc": "2.0", "method": "sum", "params": [1,2,4], "id": "1"},
+            {"jsonrpc": "2.0", "method": "notify_hello", "params": [7]}
+        ]"#;
+        let entries = serde_json::from_str::<Vec<BatchEntry>>(req).unwrap();
+        assert!(matches!(entries[0], BatchEntry::Request(_)));
+        assert!(matches!(entries[1], BatchEntry::Notification(_)));
+    }
+
+    #[test]
+    fn parse_any_input() {
+        let single = parse_any(r#" {"jsonrpc": "2.0", "method": "sum", "id": 1}"#).unwrap();
+        let req = Request::builder().id(1).method("sum").build();
+        assert_eq!(single, Incoming::Single(BatchEntry::Request(req)));
+
+        let batch = parse_any(
+            r#"
+            [{"jsonrpc": "2.0", "method": "sum", "id": 1}, {"jsonrpc": "2.0", "method": "sum"}]"#,
+        )
+        .unwrap();
+        let Incoming::Batch(batch) = batch else {
+            panic!("expected a batch");
+        };
+        assert_eq!(batch.len(), 2);
+        assert!(matches!(batch[1], BatchEntry::Notification(_)));
+
+        assert!(matches!(parse_any("  "), Err(Error::EmptyInput)));
+        assert!(matches!(parse_any("1"), Err(Error::Serde(_))));
+
+        // An empty batch is valid JSON, but an Invalid Request.
+        let err = parse_any(" []").unwrap_err();
+        let rsp = crate::response::error_response_for_parse_failure(&err);
+        assert_eq!(
+            rsp.error_kind(),
+            Some(crate::response::ErrorKind::InvalidRequest)
+        );
+    }
+
+    #[test]
+    fn empty_batch() {
+        assert!(TryInto::<Batch>::try_into("[]").is_err());
+        assert!(TryInto::<Batch>::try_into(&b" [ ] "[..]).is_err());
+        assert!(serde_json::from_str::<Batch>("[]").is_err());
+    }
+
+    #[test]
+    fn builder() {
+        let batch = Batch::builder()
+            .request(Request::builder().id(1).method("test-method").build())
+            .notification(Notification::builder().method("test-notification").build())
+            .push(BatchEntry::Request(
+                Request::builder().id(2).method("test-method").build(),
+            ))
+            .build()
+            .unwrap();
+        assert_eq!(batch.len(), 3);
+        assert!(matches!(batch[1], BatchEntry::Notification(_)));
+
+        let batch_str = TryInto::<String>::try_into(batch.clone()).unwrap();
+        let new_batch = TryInto::<Batch>::try_into(batch_str.as_str());
+        assert_eq!(new_batch.unwrap(), batch);
+    }
+
+    #[test]
+    fn builder_empty() {
+        assert!(matches!(
+            Batch::builder().build(),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn respond_in_batch_entries() {
+        let req = BatchEntry::Request(Request::builder().id(1).method("test-method").build());
+        let rsp = respond_in_batch(&req, Ok(5)).unwrap();
+        assert_eq!(rsp.id, Id::Number(1));
+        assert_eq!(rsp.status, Status::Success(serde_json::json!(5)));
+
+        let err = ResponseError {
+            code: -32000,
+            message: "busy".to_string(),
+            data: None,
+        };
+        let rsp = respond_in_batch::<()>(&req, Err(err.clone())).unwrap();
+        assert_eq!(rsp.into_error(), Err(err.clone()));
+
+        let notif =
+            BatchEntry::Notification(Notification::builder().method("test-notification").build());
+        assert!(respond_in_batch(&notif, Ok(5)).is_none());
+        assert!(respond_in_batch::<()>(&notif, Err(err)).is_none());
+    }
+
+    #[test]
+    fn deserialize_spec_batch() {
+        let rsp = r#"[
+            {"jsonrpc": "2.0", "result": 7, "id": "1"},
+            {"jsonrpc": "2.0", "result": 19, "id": "2"},
+            {"jsonrpc": "2.0", "error": {"code": -32600, "message": "Invalid Request"}, "id": null},
+            {"jsonrpc": "2.0", "error": {"code": -32601, "message": "Method not found"}, "id": "5"},
+            {"jsonrpc": "2.0", "result": ["hello", 5], "id": "9"}
+        ]"#;
+        let rsp_obj = TryInto::<BatchResponse>::try_into(rsp);
+        assert!(rsp_obj.is_ok(), "{:?}", rsp_obj.unwrap_err().to_string());
+        assert_eq!(rsp_obj.unwrap().len(), 5);
+    }
+
+    #[test]
+    fn sorted_by_reordered() {
+        let requests = [Id::from(1), Id::from("two"), Id::from(3)]
+            .map(|id| Request::builder().id(id).method("test-method").build());
+        let batch = BatchResponse(
+            [Id::from(3), Id::from(1), Id::from("two")]
+                .into_iter()
+                .map(|id| Response::new(id, Status::Success(true.into())))
+                .collect(),
+        );
+        let sorted = batch.sorted_by(&requests);
+        assert_eq!(sorted.len(), 3);
+        assert_eq!(sorted[0].as_ref().unwrap().id, Id::Number(1));
+        assert_eq!(
+            sorted[1].as_ref().unwrap().id,
+            Id::String("two".to_string())
+        );
+        assert_eq!(sorted[2].as_ref().unwrap().id, Id::Number(3));
+    }
+
+    #[test]
+    fn sorted_by_missing() {
+        let requests = [Id::from(1), Id::from(2), Id::from(3)]
+            .map(|id| Request::builder().id(id).method("test-method").build());
+        let batch = BatchResponse(
+            [Id::from(3), Id::from(1)]
+                .into_iter()
+                .map(|id| Response::new(id, Status::Success(true.into())))
+                .collect(),
+        );
+        let sorted = batch.sorted_by(&requests);
+        assert_eq!(sorted.len(), 3);
+        assert_eq!(sorted[0].as_ref().unwrap().id, Id::Number(1));
+        assert!(sorted[1].is_none());
+        assert_eq!(sorted[2].as_ref().unwrap().id, Id::Number(3));
+    }
+
+    #[test]
+    fn sorted_by_duplicate_and_null_ids() {
+        let requests = [Id::from(1), Id::from(()), Id::from(1)]
+            .map(|id| Request::builder().id(id).method("test-method").build());
+        let batch = BatchResponse(
+            [Id::from(1), Id::from(()), Id::from(1)]
+                .into_iter()
+                .map(|id| Response::new(id, Status::Success(true.into())))
+                .collect(),
+        );
+        let sorted = batch.sorted_by(&requests);
+        assert_eq!(sorted.len(), 3);
+        assert_eq!(sorted[0].as_ref().unwrap().id, Id::Number(1));
+        assert!(sorted[1].is_none());
+        assert_eq!(sorted[2].as_ref().unwrap().id, Id::Number(1));
+
+        let requests = [Id::from(1), Id::from(1)]
+            .map(|id| Request::builder().id(id).method("test-method").build());
+        let batch = BatchResponse(
+            [Id::from(1)]
+                .into_iter()
+                .map(|id| Response::new(id, Status::Success(true.into())))
+                .collect(),
+        );
+        let sorted = batch.sorted_by(&requests);
+        assert!(sorted[0].is_some());
+        assert!(sorted[1].is_none());
+    }
+}
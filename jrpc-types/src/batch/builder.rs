@@ -0,0 +1,39 @@
+//! This module implements a Builder class for the Batch object.
+
+use crate::{
+    batch::{Batch, BatchEntry},
+    error::Error,
+    notification::Notification,
+    request::Request,
+};
+
+/// The Builder class for a Batch object.
+#[must_use = "builders do nothing unless `build()` is called"]
+#[derive(Default)]
+pub struct Builder {
+    entries: Vec<BatchEntry>,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn request(self, r: Request) -> Builder {
+        self.push(BatchEntry::Request(r))
+    }
+
+    pub fn notification(self, n: Notification) -> Builder {
+        self.push(BatchEntry::Notification(n))
+    }
+
+    pub fn push(mut self, e: BatchEntry) -> Builder {
+        self.entries.push(e);
+        self
+    }
+
+    /// Builds the Batch, which fails if no entries were added as the spec forbids empty batches.
+    pub fn build(self) -> Result<Batch, Error> {
+        Batch::try_from(self.entries)
+    }
+}
@@ -0,0 +1,273 @@
+//! This module implements a `tokio_util` codec, so the JSON-RPC objects plug into `Framed`.
+//!
+//! Messages are either newline-delimited (NDJSON, the default) or framed with a
+//! Content-Length header block as in the `framing` module, see [`Framing`].
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{batch::Incoming, error::Error, framing::content_length, limits::Limits};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// How messages are delimited on the wire.
+pub enum Framing {
+    /// One message per line. Blank lines are skipped.
+    #[default]
+    Newline,
+    /// Each message is preceded by a `Content-Length` header block, as in LSP.
+    ContentLength,
+}
+
+/// Decodes `T`, by default an [`Incoming`] request, notification, or batch, and encodes any
+/// serializable JSON-RPC object.
+///
+/// Clients decode responses instead, e.g. with `JsonRpcCodec::<Response>::new(framing)`.
+/// Every frame is checked against the codec's [`Limits`] before it's parsed, by default
+/// rejecting frames over [`DEFAULT_MAX_SIZE`](crate::limits::DEFAULT_MAX_SIZE).
+#[derive(Debug, Clone)]
+pub struct JsonRpcCodec<T = Incoming> {
+    framing: Framing,
+    limits: Limits,
+    item: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T> Default for JsonRpcCodec<T> {
+    fn default() -> Self {
+        Self::new(Framing::default())
+    }
+}
+
+impl<T> JsonRpcCodec<T> {
+    pub fn new(framing: Framing) -> Self {
+        JsonRpcCodec {
+            framing,
+            limits: Limits::default(),
+            item: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the limits frames are checked against. `max_size` also bounds how much of a
+    /// frame is buffered before it's rejected.
+    pub fn with_limits(self, limits: Limits) -> Self {
+        JsonRpcCodec { limits, ..self }
+    }
+
+    fn check_size(&self, size: usize) -> Result<(), Error> {
+        if size > self.limits.max_size {
+            return Err(Error::LimitExceeded(format!(
+                "frame size {size} exceeds the maximum of {}",
+                self.limits.max_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl<T> JsonRpcCodec<T>
+where
+    T: for<'a> TryFrom<&'a [u8], Error = Error>,
+{
+    fn parse(&self, frame: &[u8]) -> Result<T, Error> {
+        self.limits.check(frame)?;
+        T::try_from(frame)
+    }
+}
+
+impl<T> Decoder for JsonRpcCodec<T>
+where
+    T: for<'a> TryFrom<&'a [u8], Error = Error>,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, Error> {
+        match self.framing {
+            Framing::Newline => loop {
+                let Some(end) = src.iter().position(|b| *b == b'\n') else {
+                    self.check_size(src.len())?;
+                    return Ok(None);
+                };
+                let line = src.split_to(end + 1);
+                if line.iter().all(u8::is_ascii_whitespace) {
+                    continue;
+                }
+                return self.parse(&line).map(Some);
+            },
+            Framing::ContentLength => {
+                let Some(end) = src.windows(4).position(|w| w == b"\r\n\r\n") else {
+                    self.check_size(src.len())?;
+                    return Ok(None);
+                };
+                let headers = std::str::from_utf8(&src[..end])
+                    .map_err(|e| Error::InvalidType(format!("headers are not UTF-8: {e}")))?;
+                // The length is untrusted, so it's checked before anything is reserved for it.
+                let len = content_length(headers.split("\r\n"))?;
+                self.check_size(len)?;
+                let start = end + 4;
+                let frame_end = start.checked_add(len).ok_or_else(|| {
+                    Error::LimitExceeded(format!("Content-Length {len} overflows the frame size"))
+                })?;
+                if src.len() < frame_end {
+                    src.reserve(frame_end - src.len());
+                    return Ok(None);
+                }
+                src.advance(start);
+                let body = src.split_to(len);
+                self.parse(&body).map(Some)
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<T>, Error> {
+        if let Some(item) = self.decode(src)? {
+            return Ok(Some(item));
+        }
+        if src.iter().all(u8::is_ascii_whitespace) {
+            src.clear();
+            return Ok(None);
+        }
+        match self.framing {
+            // The last line may not be terminated.
+            Framing::Newline => {
+                let line = src.split();
+                self.parse(&line).map(Some)
+            }
+            Framing::ContentLength => Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "incomplete frame at the end of the stream",
+            )
+            .into()),
+        }
+    }
+}
+
+impl<T, I: serde::Serialize> Encoder<I> for JsonRpcCodec<T> {
+    type Error = Error;
+
+    fn encode(&mut self, item: I, dst: &mut BytesMut) -> Result<(), Error> {
+        match self.framing {
+            Framing::Newline => {
+                serde_json::to_writer(dst.writer(), &item)?;
+                dst.put_u8(b'\n');
+            }
+            Framing::ContentLength => {
+                let body = serde_json::to_vec(&item)?;
+                dst.reserve(body.len() + 32);
+                dst.put_slice(format!("Content-Length: {}\r\n\r\n", body.len()).as_bytes());
+                dst.put_slice(&body);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{batch::BatchEntry, id::Id, request::Request, response::Response};
+
+    #[test]
+    fn newline_round_trip() {
+        let first = Request::builder().id(1).method("sum").build();
+        let second = Request::builder().id(2).method("sum").build();
+        let third = Request::builder().id(3).method("sum").build();
+        let mut codec = JsonRpcCodec::<Incoming>::default();
+        let mut buf = BytesMut::new();
+        codec.encode(&first, &mut buf).unwrap();
+        buf.put_slice(b"\r\n");
+        codec.encode(&[second], &mut buf).unwrap();
+        buf.put_slice(br#"{"jsonrpc":"2.0","method":"sum","id":3}"#);
+
+        assert_eq!(
+            codec.decode(&mut buf).unwrap(),
+            Some(Incoming::Single(BatchEntry::Request(first)))
+        );
+        assert!(matches!(
+            codec.decode(&mut buf).unwrap(),
+            Some(Incoming::Batch(batch)) if batch.len() == 1
+        ));
+        assert_eq!(codec.decode(&mut buf).unwrap(), None);
+        assert_eq!(
+            codec.decode_eof(&mut buf).unwrap(),
+            Some(Incoming::Single(BatchEntry::Request(third)))
+        );
+        assert_eq!(codec.decode_eof(&mut buf).unwrap(), None);
+    }
+
+    #[test]
+    fn content_length_round_trip() {
+        let mut codec = JsonRpcCodec::<Response>::new(Framing::ContentLength);
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result("é".into())
+            .build();
+        let mut buf = BytesMut::new();
+        codec.encode(&rsp, &mut buf).unwrap();
+        let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+        assert_eq!(
+            buf,
+            format!("Content-Length: {}\r\n\r\n{rsp_str}", rsp_str.len()).as_bytes()
+        );
+
+        let mut partial = buf.split_to(buf.len() - 1);
+        assert_eq!(codec.decode(&mut partial).unwrap(), None);
+        partial.unsplit(buf);
+        let decoded = codec.decode(&mut partial).unwrap().unwrap();
+        assert_eq!(decoded.id, Id::Number(1));
+        assert_eq!(decoded, rsp);
+        assert!(partial.is_empty());
+
+        let mut buf = BytesMut::from("Content-Length: 10\r\n\r\n[]");
+        assert!(matches!(codec.decode_eof(&mut buf), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn limits() {
+        let limits = Limits {
+            max_size: 8,
+            ..Limits::default()
+        };
+        let mut codec = JsonRpcCodec::<Incoming>::default().with_limits(limits);
+        let mut buf = BytesMut::from("[[[[[[[[[");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let mut codec = JsonRpcCodec::<Incoming>::new(Framing::ContentLength).with_limits(limits);
+        let mut buf = BytesMut::from("Content-Length: 9\r\n\r\n");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn untrusted_content_length() {
+        let mut codec = JsonRpcCodec::<Incoming>::new(Framing::ContentLength);
+        let mut buf = BytesMut::from("Content-Length: 1073741824\r\n\r\n[");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LimitExceeded(_))
+        ));
+        assert!(buf.capacity() < 1024);
+
+        let mut buf = BytesMut::from("Content-Length: 18446744073709551615\r\n\r\n[");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let unlimited = Limits {
+            max_size: usize::MAX,
+            ..Limits::default()
+        };
+        let mut codec = codec.with_limits(unlimited);
+        let mut buf = BytesMut::from("Content-Length: 18446744073709551615\r\n\r\n[");
+        assert!(matches!(
+            codec.decode(&mut buf),
+            Err(Error::LimitExceeded(_))
+        ));
+    }
+}
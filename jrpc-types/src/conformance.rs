@@ -0,0 +1,196 @@
+//! This module implements a spec conformance check for fully built JSON-RPC objects.
+//!
+//! The builders and deserializers already enforce most of the spec, but public fields can be
+//! changed afterwards. The check runs every rule in one place, e.g. in tests or at a trust
+//! boundary, and reports all violations together.
+
+use thiserror::Error as ThisError;
+
+use crate::{
+    id::Id,
+    notification::Notification,
+    params::Params,
+    request::Request,
+    response::{ErrorKind, Response, Status},
+};
+
+#[derive(Debug, Clone, PartialEq, ThisError)]
+#[non_exhaustive]
+/// A way in which a JSON-RPC object doesn't conform to the spec.
+pub enum ConformanceViolation {
+    #[error("\"jsonrpc\" is {0:?}, not \"2.0\"")]
+    Version(String),
+    #[error("\"params\" is not an object or array")]
+    UnstructuredParams,
+    #[error("method {0:?} is reserved for rpc-internal methods")]
+    ReservedMethod(String),
+    #[error("request id is null, which is discouraged")]
+    NullRequestId,
+    #[error("id {0} has a fractional part")]
+    FractionalId(f32),
+    #[error("id is not a string, number, or null")]
+    InvalidId,
+    #[error("success response has a null id, which is reserved for errors")]
+    NullSuccessId,
+    #[error("error code {0} is reserved by the spec")]
+    ReservedCode(i32),
+}
+
+fn check_version(version: &str, violations: &mut Vec<ConformanceViolation>) {
+    if version != "2.0" {
+        violations.push(ConformanceViolation::Version(version.to_string()));
+    }
+}
+
+fn check_method(method: &str, violations: &mut Vec<ConformanceViolation>) {
+    if method.starts_with("rpc.") {
+        violations.push(ConformanceViolation::ReservedMethod(method.to_string()));
+    }
+}
+
+fn check_params(params: Option<&Params>, violations: &mut Vec<ConformanceViolation>) {
+    if params.is_some_and(|p| !p.is_object() && !p.is_array()) {
+        violations.push(ConformanceViolation::UnstructuredParams);
+    }
+}
+
+fn check_id(id: &Id, violations: &mut Vec<ConformanceViolation>) {
+    match id {
+        Id::Fractional(v) => violations.push(ConformanceViolation::FractionalId(*v)),
+        Id::Raw(_) => violations.push(ConformanceViolation::InvalidId),
+        _ => {}
+    }
+}
+
+fn into_result(violations: Vec<ConformanceViolation>) -> Result<(), Vec<ConformanceViolation>> {
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+impl Request {
+    /// Checks the request against the spec, returning every violation found.
+    pub fn assert_conformant(&self) -> Result<(), Vec<ConformanceViolation>> {
+        let mut violations = Vec::new();
+        check_version(self.version(), &mut violations);
+        check_method(&self.method, &mut violations);
+        check_params(self.params.as_ref(), &mut violations);
+        if self.id == Id::Null {
+            violations.push(ConformanceViolation::NullRequestId);
+        }
+        check_id(&self.id, &mut violations);
+        into_result(violations)
+    }
+}
+
+impl Notification {
+    /// Checks the notification against the spec, returning every violation found.
+    pub fn assert_conformant(&self) -> Result<(), Vec<ConformanceViolation>> {
+        let mut violations = Vec::new();
+        check_version(self.version(), &mut violations);
+        check_method(&self.method, &mut violations);
+        check_params(self.params.as_ref(), &mut violations);
+        into_result(violations)
+    }
+}
+
+impl Response {
+    /// Checks the response against the spec, returning every violation found.
+    pub fn assert_conformant(&self) -> Result<(), Vec<ConformanceViolation>> {
+        let mut violations = Vec::new();
+        check_version(self.version(), &mut violations);
+        check_id(&self.id, &mut violations);
+        match self.status {
+            Status::Error { code, .. }
+                if ErrorKind::from_code(code) == ErrorKind::Application
+                    && (-32768..=-32000).contains(&code) =>
+            {
+                violations.push(ConformanceViolation::ReservedCode(code));
+            }
+            Status::Error { .. } => {}
+            _ if self.id == Id::Null => violations.push(ConformanceViolation::NullSuccessId),
+            _ => {}
+        }
+        into_result(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request() {
+        let mut req = Request::builder()
+            .id(1)
+            .method("subtract")
+            .params_str("[42,23]")
+            .unwrap()
+            .build();
+        assert_eq!(req.assert_conformant(), Ok(()));
+
+        req.method = "rpc.discover".to_string();
+        req.params = Some(Params(5.into()));
+        req.id = Id::Null;
+        assert_eq!(
+            req.assert_conformant(),
+            Err(vec![
+                ConformanceViolation::ReservedMethod("rpc.discover".to_string()),
+                ConformanceViolation::UnstructuredParams,
+                ConformanceViolation::NullRequestId,
+            ])
+        );
+
+        req = Request::builder().id(1.5).method("a").build();
+        assert_eq!(
+            req.assert_conformant(),
+            Err(vec![ConformanceViolation::FractionalId(1.5)])
+        );
+    }
+
+    #[test]
+    fn notification() {
+        let mut notif = Notification::new("update");
+        assert_eq!(notif.assert_conformant(), Ok(()));
+
+        notif.params = Some(Params("a".into()));
+        assert_eq!(
+            notif.assert_conformant(),
+            Err(vec![ConformanceViolation::UnstructuredParams])
+        );
+    }
+
+    #[test]
+    fn response() {
+        let rsp = Response::builder().id(1).error().method_not_found().build();
+        assert_eq!(rsp.assert_conformant(), Ok(()));
+        let rsp = Response::builder()
+            .id(Id::Null)
+            .error()
+            .parse_error()
+            .build();
+        assert_eq!(rsp.assert_conformant(), Ok(()));
+
+        let rsp = Response::builder().id(Id::Null).success().build();
+        assert_eq!(
+            rsp.assert_conformant(),
+            Err(vec![ConformanceViolation::NullSuccessId])
+        );
+
+        let rsp = Response::builder()
+            .id(0.5)
+            .error()
+            .code(-32500)
+            .message("reserved")
+            .build();
+        assert_eq!(
+            rsp.assert_conformant(),
+            Err(vec![
+                ConformanceViolation::FractionalId(0.5),
+                ConformanceViolation::ReservedCode(-32500),
+            ])
+        );
+    }
+}
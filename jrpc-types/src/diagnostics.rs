@@ -0,0 +1,109 @@
+//! This module implements the diagnostics emitted for constructs that are legal, but discouraged.
+//!
+//! Diagnostics go through `log` at [`Level::Warn`] by default. Teams that find them too noisy
+//! in production, but still want them in development, can move them to a lower level at
+//! runtime with [`set_log_level`].
+//!
+//! With the `tracing` feature they're emitted as `tracing` events instead, which takes
+//! precedence when both features are enabled.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The level diagnostics are emitted at, for either `log` or `tracing`.
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+/// Sets the level the crate's diagnostics are emitted at, [`Level::Warn`] by default.
+pub fn set_log_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Returns the level the crate's diagnostics are emitted at.
+pub fn log_level() -> Level {
+    match LEVEL.load(Ordering::Relaxed) {
+        0 => Level::Error,
+        1 => Level::Warn,
+        2 => Level::Info,
+        3 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+pub(crate) fn discouraged(args: std::fmt::Arguments) {
+    #[cfg(feature = "tracing")]
+    match log_level() {
+        Level::Error => tracing::error!("{args}"),
+        Level::Warn => tracing::warn!("{args}"),
+        Level::Info => tracing::info!("{args}"),
+        Level::Debug => tracing::debug!("{args}"),
+        Level::Trace => tracing::trace!("{args}"),
+    }
+    #[cfg(all(feature = "log", not(feature = "tracing")))]
+    {
+        let level = match log_level() {
+            Level::Error => log::Level::Error,
+            Level::Warn => log::Level::Warn,
+            Level::Info => log::Level::Info,
+            Level::Debug => log::Level::Debug,
+            Level::Trace => log::Level::Trace,
+        };
+        log::log!(level, "{args}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    // The level is global, so this is the only test changing it.
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn tracing_level() {
+        use super::*;
+
+        #[derive(Default)]
+        struct Levels(std::sync::Mutex<Vec<tracing::Level>>);
+
+        impl tracing::Subscriber for Levels {
+            fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+                true
+            }
+            fn new_span(&self, _: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+                tracing::span::Id::from_u64(1)
+            }
+            fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+            fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+            fn event(&self, event: &tracing::Event<'_>) {
+                self.0.lock().unwrap().push(*event.metadata().level());
+            }
+            fn enter(&self, _: &tracing::span::Id) {}
+            fn exit(&self, _: &tracing::span::Id) {}
+        }
+
+        let levels = std::sync::Arc::new(Levels::default());
+        tracing::subscriber::with_default(levels.clone(), || {
+            assert_eq!(log_level(), Level::Warn);
+            let _ = crate::request::Request::builder().id(()).method("").build();
+
+            set_log_level(Level::Debug);
+            assert_eq!(log_level(), Level::Debug);
+            let _ = crate::request::Request::builder().id(()).method("").build();
+            set_log_level(Level::Warn);
+        });
+        assert_eq!(
+            *levels.0.lock().unwrap(),
+            [
+                tracing::Level::WARN,
+                tracing::Level::WARN,
+                tracing::Level::DEBUG,
+                tracing::Level::DEBUG
+            ]
+        );
+    }
+}
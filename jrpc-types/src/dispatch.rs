@@ -0,0 +1,64 @@
+//! This module implements lightweight helpers for wiring requests to handlers.
+//!
+//! This is NOT a framework, it only matches method names so handlers can be picked.
+
+use crate::request::Request;
+
+/// Returns the method name of `req` if it's one of `methods`, borrowed from the request.
+pub fn dispatch<'a>(req: &'a Request, methods: &[&str]) -> Option<&'a str> {
+    methods
+        .contains(&req.method.as_str())
+        .then_some(req.method.as_str())
+}
+
+/// Matches the method of a Request, answering unknown methods with "Method not found".
+///
+/// Each arm evaluates to the `Response` for the request:
+/// ```rust
+/// use jrpc_types::{JsonRpcRequest, JsonRpcResponse, match_method};
+///
+/// let req = JsonRpcRequest::builder().id(1).method("ping").build();
+/// let rsp = match_method!(req, {
+///     "ping" => JsonRpcResponse::builder().id(&req).success().result("pong".into()).build(),
+///     "version" => JsonRpcResponse::builder().id(&req).success().result("1.0".into()).build(),
+/// });
+/// ```
+#[macro_export]
+macro_rules! match_method {
+    ($req:expr, { $($method:literal => $handler:expr),* $(,)? }) => {{
+        let req: &$crate::request::Request = &$req;
+        match req.method.as_str() {
+            $($method => $handler,)*
+            _ => req.method_not_found_response(),
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::response::{ErrorKind, Response};
+
+    #[test]
+    fn dispatch_methods() {
+        let req = Request::builder().id(1).method("add").build();
+        assert_eq!(dispatch(&req, &["sub", "add"]), Some("add"));
+        assert_eq!(dispatch(&req, &["sub"]), None);
+        assert_eq!(dispatch(&req, &[]), None);
+    }
+
+    #[test]
+    fn match_method() {
+        fn handle(req: &Request) -> Response {
+            match_method!(req, {
+                "ping" => Response::builder().id(req).success().result("pong".into()).build(),
+            })
+        }
+
+        let rsp = handle(&Request::builder().id(1).method("ping").build());
+        assert_eq!(rsp.into_error().unwrap().id, crate::id::Id::Number(1));
+
+        let rsp = handle(&Request::builder().id(1).method("pong").build());
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::MethodNotFound));
+    }
+}
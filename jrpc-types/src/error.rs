@@ -1,10 +1,99 @@
 //! This module implements the error object for jrpc-types crate.
 use thiserror::Error as ThisError;
 
+use crate::id::Id;
+
 #[derive(Debug, ThisError)]
+#[non_exhaustive]
+/// The error type for all fallible operations in this crate.
+///
+/// New variants may be added in minor releases, so a `match` on it needs a wildcard arm:
+/// ```rust
+/// use jrpc_types::{JsonRpcError, JsonRpcRequest};
+///
+/// match TryInto::<JsonRpcRequest>::try_into("{}") {
+///     Ok(req) => { /* .. */ }
+///     Err(JsonRpcError::Serde(e)) => { /* .. */ }
+///     Err(e) => { /* .. */ }
+/// }
+/// ```
 pub enum Error {
     #[error("invalid type: {0}")]
     InvalidType(String),
     #[error("serde error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("invalid version: {0}")]
+    InvalidVersion(String),
+    #[error("limit exceeded: {0}")]
+    LimitExceeded(String),
+    #[error("id mismatch: expected {expected}, got {got}")]
+    IdMismatch { expected: Id, got: Id },
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("empty input")]
+    EmptyInput,
+    /// A typed params accessor failed at `path`, e.g. "params.user.age".
+    #[error("invalid {path}: {detail}")]
+    ParamPath { path: String, detail: String },
+}
+
+/// Fails with [`Error::EmptyInput`] if `input` is empty or whitespace only, e.g. when the peer
+/// half-closed the connection without sending anything.
+pub(crate) fn check_not_empty(input: &[u8]) -> Result<(), Error> {
+    if input.iter().all(u8::is_ascii_whitespace) {
+        return Err(Error::EmptyInput);
+    }
+    Ok(())
+}
+
+impl Error {
+    /// Whether the input couldn't be parsed or deserialized (the `Serde` variant).
+    pub fn is_parse_error(&self) -> bool {
+        matches!(self, Error::Serde(_))
+    }
+
+    /// Whether a value had the wrong type or shape (the `InvalidType` variant).
+    pub fn is_invalid_type(&self) -> bool {
+        matches!(self, Error::InvalidType(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{notification::Notification, params::Params, request::Request, response::Response};
+
+    #[test]
+    fn predicates() {
+        let err = TryInto::<Request>::try_into("{").unwrap_err();
+        assert!(err.is_parse_error());
+        assert!(!err.is_invalid_type());
+
+        let params = TryInto::<Params>::try_into("[]").unwrap();
+        let err = params.require_non_empty().unwrap_err();
+        assert!(err.is_invalid_type());
+        assert!(!err.is_parse_error());
+    }
+
+    #[test]
+    fn empty_input() {
+        for input in ["", " \r\n\t"] {
+            assert!(matches!(
+                TryInto::<Request>::try_into(input),
+                Err(Error::EmptyInput)
+            ));
+            assert!(matches!(
+                TryInto::<Notification>::try_into(input.as_bytes()),
+                Err(Error::EmptyInput)
+            ));
+            assert!(matches!(
+                TryInto::<Response>::try_into(input),
+                Err(Error::EmptyInput)
+            ));
+        }
+        assert!(matches!(
+            TryInto::<Response>::try_into(" x"),
+            Err(Error::Serde(_))
+        ));
+    }
 }
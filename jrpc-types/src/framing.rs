@@ -0,0 +1,176 @@
+//! This module implements the Content-Length framing used by LSP-style transports.
+//!
+//! Each message is preceded by a header block, e.g. `Content-Length: 52\r\n\r\n`, holding the
+//! length of the body in bytes.
+
+use std::io::{BufRead, Read, Write};
+
+use crate::{error::Error, limits::Limits};
+
+/// The maximum length of a single header line, including its line break.
+const MAX_HEADER_LINE: usize = 1024;
+
+/// The maximum size of the whole header block.
+const MAX_HEADERS: usize = 8 * 1024;
+
+/// Reads one framed message body from `r`, e.g. to parse it with `TryFrom<&str>`.
+///
+/// Headers other than Content-Length (e.g. Content-Type) are ignored. The body is checked
+/// against the default [`Limits`], see [`read_framed_limited`].
+pub fn read_framed<R: BufRead>(r: &mut R) -> Result<String, Error> {
+    read_framed_limited(r, &Limits::default())
+}
+
+/// Like [`read_framed`], checking the body against `limits`.
+///
+/// The Content-Length is untrusted, so a body over `limits.max_size` fails with
+/// [`Error::LimitExceeded`] before anything is allocated for it. Header lines over 1 KiB and
+/// header blocks over 8 KiB are rejected the same way.
+pub fn read_framed_limited<R: BufRead>(r: &mut R, limits: &Limits) -> Result<String, Error> {
+    let mut headers = Vec::new();
+    let mut headers_size = 0;
+    loop {
+        let cap = MAX_HEADER_LINE.min(MAX_HEADERS - headers_size);
+        let mut line = String::new();
+        let read = r.by_ref().take(cap as u64).read_line(&mut line)?;
+        if !line.ends_with('\n') {
+            if read == cap {
+                return Err(Error::LimitExceeded(format!(
+                    "header line or block exceeds the maximum of {MAX_HEADER_LINE} or {MAX_HEADERS} bytes"
+                )));
+            }
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        headers_size += read;
+        let header = line.trim_end_matches(['\r', '\n']);
+        if header.is_empty() {
+            break;
+        }
+        headers.push(header.to_string());
+    }
+    let len = content_length(headers.iter().map(String::as_str))?;
+    if len > limits.max_size {
+        return Err(Error::LimitExceeded(format!(
+            "Content-Length {len} exceeds the maximum of {}",
+            limits.max_size
+        )));
+    }
+
+    let mut body = Vec::new();
+    r.take(len as u64).read_to_end(&mut body)?;
+    if body.len() < len {
+        return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+    }
+    limits.check(&body)?;
+    String::from_utf8(body).map_err(|e| Error::InvalidType(format!("body is not UTF-8: {e}")))
+}
+
+/// Finds the body length in the header lines of a frame, ignoring any other headers.
+pub(crate) fn content_length<'a>(
+    headers: impl IntoIterator<Item = &'a str>,
+) -> Result<usize, Error> {
+    let mut len = None;
+    for header in headers {
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(Error::InvalidType(format!("malformed header: {header}")));
+        };
+        if name.trim().eq_ignore_ascii_case("content-length") {
+            len = Some(value.trim().parse::<usize>().map_err(|e| {
+                Error::InvalidType(format!("invalid Content-Length {value:?}: {e}"))
+            })?);
+        }
+    }
+    len.ok_or_else(|| Error::InvalidType("missing Content-Length header".to_string()))
+}
+
+/// Writes `msg` to `w`, preceded by its Content-Length header.
+pub fn write_framed<W: Write>(w: &mut W, msg: &str) -> Result<(), Error> {
+    write!(w, "Content-Length: {}\r\n\r\n", msg.len())?;
+    w.write_all(msg.as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::request::Request;
+
+    #[test]
+    fn framed_round_trip() {
+        let req = Request::builder().id(1).method("initialize").build();
+        let req_str = TryInto::<String>::try_into(req.clone()).unwrap();
+        let mut buf = Vec::new();
+        write_framed(&mut buf, &req_str).unwrap();
+        write_framed(&mut buf, "{\"é\":1}").unwrap();
+        assert!(buf.starts_with(format!("Content-Length: {}\r\n\r\n", req_str.len()).as_bytes()));
+
+        let mut r = buf.as_slice();
+        let body = read_framed(&mut r).unwrap();
+        assert_eq!(TryInto::<Request>::try_into(body.as_str()).unwrap(), req);
+        assert_eq!(read_framed(&mut r).unwrap(), "{\"é\":1}");
+        assert!(matches!(read_framed(&mut r), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn framed_headers() {
+        let data = "content-type: application/vscode-jsonrpc; charset=utf-8\r\nCONTENT-LENGTH: 2\r\n\r\n[]";
+        assert_eq!(read_framed(&mut data.as_bytes()).unwrap(), "[]");
+
+        let data = "Content-Type: x\r\n\r\n[]";
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::InvalidType(_))
+        ));
+
+        let data = "Content-Length: two\r\n\r\n[]";
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::InvalidType(_))
+        ));
+
+        let data = "Content-Length: 10\r\n\r\n[]";
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn framed_limits() {
+        let data = "Content-Length: 18446744073709551615\r\n\r\n[]";
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let limits = Limits {
+            max_size: 1,
+            ..Limits::default()
+        };
+        let data = "Content-Length: 2\r\n\r\n[]";
+        assert!(matches!(
+            read_framed_limited(&mut data.as_bytes(), &limits),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        // A peer that never ends its header line.
+        let data = "X".repeat(MAX_HEADER_LINE * 4);
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        // Or never ends its header block.
+        let data = "X-Padding: 1\r\n".repeat(MAX_HEADERS);
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let data = "Content-Length: 2\r\n";
+        assert!(matches!(
+            read_framed(&mut data.as_bytes()),
+            Err(Error::Io(_))
+        ));
+    }
+}
@@ -1,9 +1,11 @@
 //! This module implements the structure for the "id" field in JSON-RPC objects.
 
+use std::sync::atomic::{AtomicI64, Ordering};
+
 use crate::error::Error;
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
-#[serde(untagged)] // TODO: We can make this more efficient by writing our own deserialization impl
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(untagged)]
 /// This object implements the "id" field in JSON-RPC objects.
 ///
 /// "id" can only be String, Number (including Fractional), or Null
@@ -12,6 +14,195 @@ pub enum Id {
     Number(i64),
     Fractional(f32),
     Null,
+    /// Any other JSON value, e.g. an object, sent by a non-conformant peer.
+    ///
+    /// This is an interop-only escape hatch, so a bridge can echo back whatever id it received.
+    /// It's NOT spec conformant and never produced by parsing without the `raw-id` feature.
+    Raw(serde_json::Value),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// The type of an [`Id`], without its value, e.g. for counting the id types a server sees.
+pub enum IdKind {
+    String,
+    Number,
+    Fractional,
+    Null,
+    Raw,
+}
+
+impl Id {
+    pub fn kind(&self) -> IdKind {
+        match self {
+            Id::String(_) => IdKind::String,
+            Id::Number(_) => IdKind::Number,
+            Id::Fractional(_) => IdKind::Fractional,
+            Id::Null => IdKind::Null,
+            Id::Raw(_) => IdKind::Raw,
+        }
+    }
+
+    /// Creates a Number id from a millisecond timestamp.
+    pub fn from_timestamp_millis(ms: i64) -> Id {
+        Id::Number(ms)
+    }
+
+    /// Creates a Number id from the current time in milliseconds since the Unix epoch.
+    ///
+    /// Two calls within the same millisecond produce the same id, use a counter if uniqueness matters.
+    #[cfg(feature = "time")]
+    pub fn now() -> Id {
+        let ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis());
+        Id::from_timestamp_millis(i64::try_from(ms).unwrap_or(i64::MAX))
+    }
+
+    /// Returns the String value without consuming the Id, or None for any other type.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Id::String(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Returns the Number value without consuming the Id, or None for any other type.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Id::Number(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Creates a Number id from an unsigned counter, failing with [`Error::InvalidType`] if it
+    /// overflows `i64` instead of wrapping to a negative id that would correlate wrongly.
+    pub fn try_from_u64(v: u64) -> Result<Id, Error> {
+        i64::try_from(v)
+            .map(Id::Number)
+            .map_err(|_| Error::InvalidType(format!("id {v} overflows a Number id")))
+    }
+
+    /// Like [`Id::try_from_u64`], for `usize` counters such as indices.
+    pub fn try_from_usize(v: usize) -> Result<Id, Error> {
+        Id::try_from_u64(v as u64)
+    }
+
+    /// Serializes the bare id, e.g. `42`, `"abc"`, `1.2`, or `null`.
+    #[must_use]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string(self).expect("an Id always serializes to JSON")
+    }
+
+    /// Parses a bare id value, e.g. one sent outside a JSON-RPC object in a header.
+    ///
+    /// Anything other than a string, number, or null is rejected with [`Error::InvalidType`],
+    /// unless the `raw-id` feature is enabled.
+    pub fn from_json_str(s: &str) -> Result<Id, Error> {
+        let value = serde_json::from_str::<serde_json::Value>(s)?;
+        Id::try_from(value)
+    }
+}
+
+/// An id equals a string only if it's a String id with the same value.
+impl PartialEq<str> for Id {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == Some(other)
+    }
+}
+
+impl PartialEq<&str> for Id {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == Some(*other)
+    }
+}
+
+/// Fractional ids compare by value, except that a NaN equals a NaN with the same bits, e.g. one
+/// created with `Id::from(f32::NAN)`, so every id is equal to itself as `Eq` requires.
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Id::String(a), Id::String(b)) => a == b,
+            (Id::Number(a), Id::Number(b)) => a == b,
+            (Id::Fractional(a), Id::Fractional(b)) => a == b || a.to_bits() == b.to_bits(),
+            (Id::Null, Id::Null) => true,
+            (Id::Raw(a), Id::Raw(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Id {}
+
+impl std::hash::Hash for Id {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Id::String(v) => v.hash(state),
+            Id::Number(v) => v.hash(state),
+            // 0.0 and -0.0 are equal, so they must hash the same.
+            Id::Fractional(v) if *v == 0.0 => 0u32.hash(state),
+            Id::Fractional(v) => v.to_bits().hash(state),
+            Id::Null => {}
+            Id::Raw(v) => crate::params::hash_value(v, state),
+        }
+    }
+}
+
+/// Renders a stable textual key for the Id, e.g. for correlating request and response logs.
+///
+/// Numbers and fractionals render in decimal form, strings render raw, and null renders as "null".
+impl std::fmt::Display for Id {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Id::String(v) => f.write_str(v),
+            Id::Number(v) => write!(f, "{v}"),
+            Id::Fractional(v) => write!(f, "{v}"),
+            Id::Null => f.write_str("null"),
+            Id::Raw(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl TryFrom<serde_json::Value> for Id {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        match value {
+            serde_json::Value::String(v) => Ok(Id::String(v)),
+            serde_json::Value::Number(v) => match v.as_i64() {
+                Some(v) => Ok(Id::Number(v)),
+                // Numbers beyond the f32 range would turn into an infinite id.
+                None => match v.as_f64().unwrap_or_default() as f32 {
+                    f if f.is_finite() => Ok(Id::Fractional(f)),
+                    _ => Err(Error::InvalidType(format!(
+                        "id {v} is out of range for a Fractional id"
+                    ))),
+                },
+            },
+            serde_json::Value::Null => Ok(Id::Null),
+            v if cfg!(feature = "raw-id") => Ok(Id::Raw(v)),
+            serde_json::Value::Bool(_) => Err(Error::InvalidType(
+                "cannot convert Value type Bool to Id".to_string(),
+            )),
+            serde_json::Value::Array(_) => Err(Error::InvalidType(
+                "cannot convert Value type Array to Id".to_string(),
+            )),
+            serde_json::Value::Object(_) => Err(Error::InvalidType(
+                "cannot convert Value type Object to Id".to_string(),
+            )),
+        }
+    }
+}
+
+/// Non-scalar ids are only accepted with the `raw-id` feature, read as [`Id::Raw`].
+impl<'de> serde::Deserialize<'de> for Id {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Id::try_from(value).map_err(serde::de::Error::custom)
+    }
 }
 
 impl From<&str> for Id {
@@ -35,6 +226,9 @@ impl TryFrom<Id> for String {
             Id::Null => Err(Error::InvalidType(
                 "cannot convert Id type Null to String".to_string(),
             )),
+            Id::Raw(_) => Err(Error::InvalidType(
+                "cannot convert Id type Raw to String".to_string(),
+            )),
         }
     }
 }
@@ -45,6 +239,22 @@ impl From<i64> for Id {
     }
 }
 
+impl TryFrom<u64> for Id {
+    type Error = Error;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Id::try_from_u64(value)
+    }
+}
+
+impl TryFrom<usize> for Id {
+    type Error = Error;
+
+    fn try_from(value: usize) -> Result<Self, Self::Error> {
+        Id::try_from_usize(value)
+    }
+}
+
 impl TryFrom<Id> for i64 {
     type Error = Error;
 
@@ -60,6 +270,9 @@ impl TryFrom<Id> for i64 {
             Id::Null => Err(Error::InvalidType(
                 "cannot convert Id type Null to Number".to_string(),
             )),
+            Id::Raw(_) => Err(Error::InvalidType(
+                "cannot convert Id type Raw to Number".to_string(),
+            )),
         }
     }
 }
@@ -85,6 +298,9 @@ impl TryFrom<Id> for f32 {
             Id::Null => Err(Error::InvalidType(
                 "cannot convert Id type Null to Fractional".to_string(),
             )),
+            Id::Raw(_) => Err(Error::InvalidType(
+                "cannot convert Id type Raw to Fractional".to_string(),
+            )),
         }
     }
 }
@@ -110,8 +326,88 @@ impl TryFrom<Id> for () {
                 "cannot convert Id type Fractional to ()".to_string(),
             )),
             Id::Null => Ok(()),
+            Id::Raw(_) => Err(Error::InvalidType(
+                "cannot convert Id type Raw to ()".to_string(),
+            )),
+        }
+    }
+}
+
+/// Generates unique, increasing Number ids, e.g. for a client's outgoing requests.
+///
+/// The generator is `Send + Sync`, so it can be shared between threads and tasks. Once
+/// `i64::MAX` is reached it wraps around to 0, so the produced ids are never negative.
+#[derive(Debug, Default)]
+pub struct IdGenerator {
+    next: AtomicI64,
+}
+
+impl IdGenerator {
+    /// Creates a generator whose first id is 0.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a generator whose first id is `start`.
+    ///
+    /// Panics if `start` is negative, since the generated ids are never negative.
+    pub fn with_start(start: i64) -> Self {
+        assert!(start >= 0, "IdGenerator start {start} is negative");
+        IdGenerator {
+            next: AtomicI64::new(start),
         }
     }
+
+    /// Returns the next id, wrapping around to 0 after `i64::MAX`.
+    pub fn next(&self) -> Id {
+        Id::Number(self.next.fetch_add(1, Ordering::Relaxed) & i64::MAX)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Id {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Id".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        // The derived schema for an untagged enum would describe each variant separately,
+        // but on the wire "id" is simply one of these JSON types.
+        schemars::json_schema!({
+            "description": "A JSON-RPC \"id\" is a String, Number, or Null value.",
+            "type": ["string", "number", "null"]
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Id {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3u8)? {
+            0 => Id::String(u.arbitrary()?),
+            1 => Id::Number(u.arbitrary()?),
+            2 => {
+                // NaN and infinity have no JSON representation.
+                let v: f32 = u.arbitrary()?;
+                Id::Fractional(if v.is_finite() { v } else { 0.5 })
+            }
+            _ => Id::Null,
+        })
+    }
+}
+
+/// A `proptest` strategy producing any valid [`Id`].
+#[cfg(feature = "proptest")]
+pub fn arb_id() -> impl proptest::strategy::Strategy<Value = Id> {
+    use proptest::prelude::*;
+
+    prop_oneof![
+        any::<String>().prop_map(Id::String),
+        any::<i64>().prop_map(Id::Number),
+        // NaN and infinity have no JSON representation.
+        (proptest::num::f32::NORMAL | proptest::num::f32::ZERO).prop_map(Id::Fractional),
+        Just(Id::Null),
+    ]
 }
 
 #[cfg(test)]
@@ -123,6 +419,77 @@ mod tests {
         pub id: Id,
     }
 
+    #[test]
+    fn eq_str() {
+        let id = Id::String("init".to_string());
+        assert!(id == "init");
+        assert!(id == *"init");
+        assert!(id != "other");
+        assert!(Id::Number(1) != "1");
+        assert!(Id::Fractional(1.5) != "1.5");
+        assert!(Id::Null != "null");
+        assert!(Id::Null != "");
+    }
+
+    #[test]
+    fn id_json_string() {
+        for (id, json) in [
+            (Id::Number(42), "42"),
+            (Id::String("abc".to_string()), r#""abc""#),
+            (Id::Fractional(1.2), "1.2"),
+            (Id::Null, "null"),
+        ] {
+            assert_eq!(id.to_json_string(), json);
+            assert_eq!(Id::from_json_str(json).unwrap(), id);
+        }
+
+        #[cfg(not(feature = "raw-id"))]
+        {
+            assert!(matches!(
+                Id::from_json_str(r#"{"a":1}"#),
+                Err(Error::InvalidType(_))
+            ));
+            assert!(matches!(
+                Id::from_json_str("[1]"),
+                Err(Error::InvalidType(_))
+            ));
+        }
+        assert!(matches!(Id::from_json_str("abc"), Err(Error::Serde(_))));
+    }
+
+    #[test]
+    fn id_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let hasher = RandomState::new();
+        assert_eq!(
+            hasher.hash_one(Id::Fractional(0.0)),
+            hasher.hash_one(Id::Fractional(-0.0))
+        );
+        assert_ne!(
+            hasher.hash_one(Id::Number(1)),
+            hasher.hash_one(Id::String("1".to_string()))
+        );
+        assert_eq!(hasher.hash_one(Id::Null), hasher.hash_one(Id::Null));
+
+        let nan = Id::from(f32::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_eq!(hasher.hash_one(&nan), hasher.hash_one(nan.clone()));
+        assert_ne!(nan, Id::Fractional(0.0));
+        assert_eq!(Id::Fractional(0.0), Id::Fractional(-0.0));
+    }
+
+    #[test]
+    fn non_finite_id() {
+        let id = Id::try_from(serde_json::json!(1e39));
+        assert!(matches!(id, Err(Error::InvalidType(_))));
+        assert!(serde_json::from_str::<TestObject>(r#"{"id":1e39}"#).is_err());
+        assert_eq!(
+            Id::try_from(serde_json::json!(1e38)).unwrap(),
+            Id::Fractional(1e38)
+        );
+    }
+
     #[test]
     fn string_id() {
         let initial_id = "string-id";
@@ -145,7 +512,7 @@ mod tests {
     #[test]
     fn number_id() {
         let initial_id = 25;
-        let to_id: Id = initial_id.clone().into();
+        let to_id: Id = initial_id.into();
         let from_id = TryInto::<i64>::try_into(to_id);
         assert!(from_id.is_ok());
         assert_eq!(from_id.unwrap(), initial_id);
@@ -161,10 +528,34 @@ mod tests {
         assert_eq!(to_str.unwrap(), test_obj_str);
     }
 
+    #[test]
+    fn id_kind() {
+        assert_eq!(Id::from("a").kind(), IdKind::String);
+        assert_eq!(Id::from(1).kind(), IdKind::Number);
+        assert_eq!(Id::from(1.5).kind(), IdKind::Fractional);
+        assert_eq!(Id::Null.kind(), IdKind::Null);
+        assert_eq!(Id::Raw(serde_json::json!({})).kind(), IdKind::Raw);
+    }
+
+    #[test]
+    fn unsigned_id() {
+        assert_eq!(Id::try_from_u64(42).unwrap(), Id::Number(42));
+        assert_eq!(Id::try_from(i64::MAX as u64).unwrap(), Id::Number(i64::MAX));
+        assert!(matches!(
+            Id::try_from(i64::MAX as u64 + 1),
+            Err(Error::InvalidType(_))
+        ));
+        assert!(matches!(
+            Id::try_from_u64(u64::MAX),
+            Err(Error::InvalidType(_))
+        ));
+        assert_eq!(Id::try_from(7usize).unwrap(), Id::Number(7));
+    }
+
     #[test]
     fn fractional_id() {
         let initial_id = 1.2;
-        let to_id: Id = initial_id.clone().into();
+        let to_id: Id = initial_id.into();
         let from_id = TryInto::<f32>::try_into(to_id);
         assert!(from_id.is_ok());
         assert_eq!(from_id.unwrap(), initial_id);
@@ -181,9 +572,10 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::unit_cmp)] // the round-trip is what's tested, even for the unit value
     fn null_id() {
         let initial_id = ();
-        let to_id: Id = initial_id.clone().into();
+        let to_id: Id = initial_id.into();
         let from_id = TryInto::<()>::try_into(to_id);
         assert!(from_id.is_ok());
         assert_eq!(from_id.unwrap(), initial_id);
@@ -198,15 +590,91 @@ mod tests {
         assert_eq!(to_str.unwrap(), test_obj_str);
     }
 
+    #[test]
+    fn timestamp_id() {
+        assert_eq!(
+            Id::from_timestamp_millis(1700000000000),
+            Id::Number(1700000000000)
+        );
+
+        #[cfg(feature = "time")]
+        assert!(matches!(Id::now(), Id::Number(ms) if ms >= 1700000000000));
+    }
+
+    #[test]
+    fn id_generator() {
+        let generator = IdGenerator::new();
+        assert_eq!(generator.next(), Id::Number(0));
+        assert_eq!(generator.next(), Id::Number(1));
+
+        let generator = IdGenerator::with_start(i64::MAX);
+        assert_eq!(generator.next(), Id::Number(i64::MAX));
+        assert_eq!(generator.next(), Id::Number(0));
+
+        let generator = std::sync::Arc::new(IdGenerator::with_start(1));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let generator = generator.clone();
+                std::thread::spawn(move || (0..100).map(|_| generator.next()).collect::<Vec<_>>())
+            })
+            .collect();
+        let mut ids: Vec<i64> = handles
+            .into_iter()
+            .flat_map(|h| h.join().unwrap())
+            .map(|id| i64::try_from(id).unwrap())
+            .collect();
+        ids.sort();
+        assert_eq!(ids, (1..=400).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn id_generator_negative_start() {
+        IdGenerator::with_start(-5);
+    }
+
+    #[test]
+    fn value_id() {
+        let id = Id::try_from(serde_json::json!("abc"));
+        assert_eq!(id.unwrap(), Id::String("abc".to_string()));
+        let id = Id::try_from(serde_json::json!(42));
+        assert_eq!(id.unwrap(), Id::Number(42));
+        let id = Id::try_from(serde_json::json!(1.5));
+        assert_eq!(id.unwrap(), Id::Fractional(1.5));
+        let id = Id::try_from(serde_json::Value::Null);
+        assert_eq!(id.unwrap(), Id::Null);
+
+        #[cfg(not(feature = "raw-id"))]
+        {
+            let id = Id::try_from(serde_json::json!({"test": "id"}));
+            assert!(matches!(id, Err(Error::InvalidType(_))));
+            let id = Id::try_from(serde_json::json!(["test", "id"]));
+            assert!(matches!(id, Err(Error::InvalidType(_))));
+            let id = Id::try_from(serde_json::json!(true));
+            assert!(matches!(id, Err(Error::InvalidType(_))));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Id::from(42).to_string(), "42");
+        assert_eq!(Id::from("abc").to_string(), "abc");
+        assert_eq!(Id::from(1.5).to_string(), "1.5");
+        assert_eq!(Id::from(()).to_string(), "null");
+    }
+
     #[test]
     fn negative_serde_tests() {
-        // id as object
-        let obj = r#"{"id":{"test":"id"}}"#;
-        assert!(serde_json::from_str::<TestObject>(obj).is_err());
+        #[cfg(not(feature = "raw-id"))]
+        {
+            // id as object
+            let obj = r#"{"id":{"test":"id"}}"#;
+            assert!(serde_json::from_str::<TestObject>(obj).is_err());
 
-        // id as array
-        let obj = r#"{"id":["test","id"]}"#;
-        assert!(serde_json::from_str::<TestObject>(obj).is_err());
+            // id as array
+            let obj = r#"{"id":["test","id"]}"#;
+            assert!(serde_json::from_str::<TestObject>(obj).is_err());
+        }
 
         // id missing
         let obj = r#"{}"#;
@@ -0,0 +1,81 @@
+//! This module implements a bounded interner for method names.
+//!
+//! Interning trades a lock and a hash lookup per name for not allocating hot method names over
+//! and over: a hit costs a reference count increment, while a miss also allocates once and, up
+//! to [`CAPACITY`] names, keeps the name alive for the rest of the process. Names past the
+//! capacity are still returned, just not kept, so untrusted peers can't grow the interner
+//! without bound.
+//!
+//! `Request::method` stays a `String`, since changing its type with a feature would break other
+//! crates in the same build. Use [`deserialize_method`] on your own request types instead.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+/// The maximum number of method names kept by the interner.
+pub const CAPACITY: usize = 1024;
+
+static INTERNER: LazyLock<Mutex<HashSet<Arc<str>>>> = LazyLock::new(Default::default);
+
+/// Returns the shared copy of the method name `name`.
+pub fn method(name: &str) -> Arc<str> {
+    let mut interner = INTERNER.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(interned) = interner.get(name) {
+        return interned.clone();
+    }
+    let interned = Arc::<str>::from(name);
+    if interner.len() < CAPACITY {
+        interner.insert(interned.clone());
+    }
+    interned
+}
+
+/// Deserializes a method name through the interner, for use with
+/// `#[serde(deserialize_with = "jrpc_types::intern::deserialize_method")]`.
+pub fn deserialize_method<'de, D>(deserializer: D) -> Result<Arc<str>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct MethodVisitor;
+    impl serde::de::Visitor<'_> for MethodVisitor {
+        type Value = Arc<str>;
+
+        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+            formatter.write_str("a method name")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+            Ok(method(v))
+        }
+    }
+    deserializer.deserialize_str(MethodVisitor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_methods() {
+        #[derive(serde::Deserialize)]
+        struct Call {
+            #[serde(deserialize_with = "deserialize_method")]
+            method: Arc<str>,
+        }
+
+        let a: Call = serde_json::from_str(r#"{"method":"eth_call"}"#).unwrap();
+        let b: Call = serde_json::from_str(r#"{"method":"eth_call"}"#).unwrap();
+        assert_eq!(&*a.method, "eth_call");
+        assert!(Arc::ptr_eq(&a.method, &b.method));
+        assert!(serde_json::from_str::<Call>(r#"{"method":1}"#).is_err());
+
+        for i in 0..CAPACITY {
+            method(&format!("method-{i}"));
+        }
+        assert!(Arc::ptr_eq(&method("eth_call"), &a.method));
+        let name = format!("method-{CAPACITY}");
+        assert!(!Arc::ptr_eq(&method(&name), &method(&name)));
+    }
+}
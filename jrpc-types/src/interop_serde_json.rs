@@ -0,0 +1,117 @@
+//! This module implements conversions between the JSON-RPC objects and `serde_json::Value`.
+//!
+//! `serde_json::Value` is the interchange format with other JSON-RPC crates. The Value produced
+//! for an object is exactly its spec wire format:
+//!
+//! - Request: `{"jsonrpc": "2.0", "method": ..., "params": ..., "id": ...}`, without "params"
+//!   when there are none.
+//! - Notification: the same as a Request, without "id".
+//! - Response: `{"jsonrpc": "2.0", "id": ..., "result": ...}` on success, or
+//!   `{"jsonrpc": "2.0", "id": ..., "error": {"code": ..., "message": ..., "data": ...}}` on
+//!   error, without "data" when there is none.
+//!
+//! Converting from a Value applies the same validation as parsing from a string.
+
+use crate::{error::Error, notification::Notification, request::Request, response::Response};
+
+impl TryFrom<Request> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(value: Request) -> Result<Self, Self::Error> {
+        serde_json::to_value(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<serde_json::Value> for Request {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<Notification> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(value: Notification) -> Result<Self, Self::Error> {
+        serde_json::to_value(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<serde_json::Value> for Notification {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<Response> for serde_json::Value {
+    type Error = Error;
+
+    fn try_from(value: Response) -> Result<Self, Self::Error> {
+        serde_json::to_value(value).map_err(|e| e.into())
+    }
+}
+
+impl TryFrom<serde_json::Value> for Response {
+    type Error = Error;
+
+    fn try_from(value: serde_json::Value) -> Result<Self, Self::Error> {
+        serde_json::from_value(value).map_err(|e| e.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn request_value() {
+        let req = Request::builder()
+            .id(1)
+            .method("subtract")
+            .params_str("[42,23]")
+            .unwrap()
+            .build();
+        let value = serde_json::Value::try_from(req.clone()).unwrap();
+        assert_eq!(
+            value,
+            json!({"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id": 1})
+        );
+        assert_eq!(Request::try_from(value).unwrap(), req);
+
+        assert!(Request::try_from(json!({"jsonrpc": "1.0", "method": "a", "id": 1})).is_err());
+    }
+
+    #[test]
+    fn notification_value() {
+        let notif = Notification::builder().method("update").build();
+        let value = serde_json::Value::try_from(notif.clone()).unwrap();
+        assert_eq!(value, json!({"jsonrpc": "2.0", "method": "update"}));
+        assert_eq!(Notification::try_from(value).unwrap(), notif);
+    }
+
+    #[test]
+    fn response_value() {
+        let rsp = Response::builder()
+            .id("a")
+            .success()
+            .result(json!(19))
+            .build();
+        let value = serde_json::Value::try_from(rsp.clone()).unwrap();
+        assert_eq!(value, json!({"jsonrpc": "2.0", "id": "a", "result": 19}));
+        assert_eq!(Response::try_from(value).unwrap(), rsp);
+
+        let rsp = Response::builder().id(()).error().parse_error().build();
+        let value = serde_json::Value::try_from(rsp.clone()).unwrap();
+        assert_eq!(
+            value,
+            json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": "Parse error"}})
+        );
+        assert_eq!(Response::try_from(value).unwrap(), rsp);
+
+        assert!(Response::try_from(json!({"jsonrpc": "2.0", "id": 1})).is_err());
+    }
+}
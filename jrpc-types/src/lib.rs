@@ -16,6 +16,27 @@
 //! - **serde**: used for (de)serialization
 //! - **serde_json**: used for JSON de(serialization) implementation
 //! - **thiserror**: used for error reporting
+//! - **serde_path_to_error**: used to report where typed params extraction failed
+//!
+//! # Features
+//!
+//! - **schemars**: derives `schemars::JsonSchema` for the public types, so tooling (e.g. OpenRPC) can describe the JSON-RPC envelope.
+//! - **arbitrary**: implements `arbitrary::Arbitrary` for the public types, always generating spec-valid objects for fuzzing.
+//! - **proptest**: exposes `proptest` strategies (e.g. `request::arb_request()`) producing spec-valid objects for property-based testing.
+//! - **log**: emits diagnostics through `log` for constructs that are legal but discouraged (e.g. an empty method name, or a null id in a request). They're logged at warn level by default, see `diagnostics::set_log_level` to move them to e.g. debug.
+//! - **time**: adds `Id::now()`, creating an id from the current millisecond timestamp.
+//! - **base64**: adds helpers for binary results encoded as base64 strings. This is a convention, not part of the spec.
+//! - **case-insensitive**: also accepts capitalized and uppercase field names (e.g. "Method" or "ID") from legacy peers. This is NOT spec conformant.
+//! - **raw**: adds `SuccessBuilder::result_raw`, emitting an already serialized result verbatim, e.g. when proxying upstream responses.
+//! - **tagged-debug**: adds `Response::to_tagged_string`, an explicitly tagged form (e.g. `"status":"success"`) for logs and tooling. This is NOT the spec's wire format.
+//! - **tokio**: adds `stream::response_stream`, reading line-delimited responses from a tokio `AsyncBufRead`.
+//! - **interop-serde_json**: adds conversions between the JSON-RPC objects and `serde_json::Value`, the interchange format with other JSON-RPC crates.
+//! - **raw-id**: accepts non-conformant ids (e.g. objects) when parsing, preserved as `Id::Raw` so a bridge can echo them back. This is NOT spec conformant.
+//! - **tracing**: emits the same diagnostics as `log` through `tracing` instead, at the same configurable level, preferred when both are enabled.
+//! - **meta**: adds an optional `meta` field to `Request` and `Response`, used by some frameworks and only serialized when present. This is an extension beyond the spec.
+//! - **intern**: adds `intern::deserialize_method`, sharing hot method names from a bounded interner instead of allocating each one. This trades a lock and lookup per name for fewer allocations, see the `intern` module.
+//! - **bytes**: adds `encode_into` on `Request`, `Notification`, and `Response`, serializing straight into a `bytes::BytesMut` (e.g. in a `tokio_util` codec). Depends on the `bytes` crate.
+//! - **codec**: adds `codec::JsonRpcCodec`, a `tokio_util` `Encoder`/`Decoder` for `Framed` transports, with newline-delimited or Content-Length framing. Depends on `tokio-util` and enables **bytes**.
 //!
 //! # Usage
 //!
@@ -49,6 +70,8 @@
 //! }
 //! ```
 //!
+//! Peers that deviate from the spec (e.g. sending a string error code) can be accepted per call with `parse_with` and `options::ParseOptions`.
+//!
 //! ## Notifications
 //!
 //! JSON-RPC Notifications are pretty much Requests, without an ID... You can build Notifications like:
@@ -144,15 +167,35 @@
 //!     .build();
 //! ```
 
+pub mod batch;
+#[cfg(feature = "codec")]
+pub mod codec;
+pub mod conformance;
+#[cfg(any(feature = "log", feature = "tracing"))]
+pub mod diagnostics;
+pub mod dispatch;
 pub mod error;
+pub mod framing;
 pub mod id;
+#[cfg(feature = "intern")]
+pub mod intern;
+#[cfg(feature = "interop-serde_json")]
+pub mod interop_serde_json;
+pub mod limits;
 pub mod notification;
+pub mod options;
 pub mod params;
 pub mod request;
 pub mod response;
+#[cfg(feature = "tokio")]
+pub mod stream;
 pub mod version;
 
+pub use batch::Batch as JsonRpcBatch;
+pub use batch::BatchEntry as JsonRpcBatchEntry;
+pub use batch::BatchResponse as JsonRpcBatchResponse;
 pub use error::Error as JsonRpcError;
 pub use notification::Notification as JsonRpcNotification;
 pub use request::Request as JsonRpcRequest;
 pub use response::Response as JsonRpcResponse;
+pub use response::ResponseError as JsonRpcResponseError;
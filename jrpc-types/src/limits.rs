@@ -0,0 +1,110 @@
+//! This module implements limits guarding the parsing of untrusted input.
+
+use crate::error::Error;
+
+/// The default maximum input size, 8 MiB.
+pub const DEFAULT_MAX_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Limits applied to input before it's deserialized, e.g. by `Request::try_from_limited`.
+pub struct Limits {
+    /// The maximum input size in bytes, [`DEFAULT_MAX_SIZE`] by default.
+    pub max_size: usize,
+    /// The maximum nesting depth of arrays and objects, where the enclosing object counts as 1.
+    /// 64 by default.
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_size: DEFAULT_MAX_SIZE,
+            max_depth: 64,
+        }
+    }
+}
+
+impl Limits {
+    /// Checks `input` against the limits with a single non-recursive scan.
+    ///
+    /// This doesn't validate the JSON itself, that's left to the deserializer.
+    pub fn check(&self, input: &[u8]) -> Result<(), Error> {
+        if input.len() > self.max_size {
+            return Err(Error::LimitExceeded(format!(
+                "input size {} exceeds the maximum of {}",
+                input.len(),
+                self.max_size
+            )));
+        }
+
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+        for &b in input {
+            if in_string {
+                match b {
+                    _ if escaped => escaped = false,
+                    b'\\' => escaped = true,
+                    b'"' => in_string = false,
+                    _ => {}
+                }
+                continue;
+            }
+            match b {
+                b'"' => in_string = true,
+                b'[' | b'{' => {
+                    depth += 1;
+                    if depth > self.max_depth {
+                        return Err(Error::LimitExceeded(format!(
+                            "nesting depth exceeds the maximum of {}",
+                            self.max_depth
+                        )));
+                    }
+                }
+                b']' | b'}' => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_depth() {
+        let limits = Limits {
+            max_depth: 3,
+            ..Default::default()
+        };
+        assert!(limits.check(br#"{"params":[[1]]}"#).is_ok());
+        assert!(matches!(
+            limits.check(br#"{"params":[[[1]]]}"#),
+            Err(Error::LimitExceeded(_))
+        ));
+        assert!(limits.check(br#"{"params":["[[[[", "\"{{{{"]}"#).is_ok());
+        assert!(limits.check(br#"[1],[2],[3],[4]"#).is_ok());
+    }
+
+    #[test]
+    fn max_size() {
+        let limits = Limits {
+            max_size: 4,
+            ..Default::default()
+        };
+        assert!(limits.check(b"[1]").is_ok());
+        assert!(matches!(
+            limits.check(b"[1,2]"),
+            Err(Error::LimitExceeded(_))
+        ));
+
+        let input = vec![b' '; DEFAULT_MAX_SIZE + 1];
+        assert!(Limits::default().check(&input[1..]).is_ok());
+        assert!(matches!(
+            Limits::default().check(&input),
+            Err(Error::LimitExceeded(_))
+        ));
+    }
+}
@@ -1,15 +1,32 @@
 //! This module implements the notification JSON-RPC object.
 
-use crate::{error::Error, params::Params};
+use crate::{error::Error, options::ParseOptions, params::Params, version::Version};
 
 pub mod builder;
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The JSON-RPC Notification Object
 pub struct Notification {
-    #[serde(deserialize_with = "crate::version::version_deserialize")]
-    jsonrpc: String,
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Jsonrpc", alias = "JSONRPC")
+    )]
+    jsonrpc: Version,
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Method", alias = "METHOD")
+    )]
     pub method: String,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::params::deserialize_optional"
+    )]
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Params", alias = "PARAMS")
+    )]
     pub params: Option<Params>,
 }
 
@@ -17,16 +34,92 @@ impl Notification {
     pub fn builder() -> builder::Builder<builder::MethodNone> {
         builder::Builder::new()
     }
+
+    /// Parses a notification with `options`, e.g. to accept the deviations of a non-conformant
+    /// peer.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<Notification, Error> {
+        crate::options::with(options, || Notification::try_from(value))
+    }
+
+    /// Creates a notification without params, e.g. for a heartbeat or ping.
+    ///
+    /// Params are optional in the spec, so this is a perfectly valid notification.
+    pub fn new<T: Into<String>>(method: T) -> Notification {
+        Notification::builder().method(method).build()
+    }
+
+    /// Moves the params out without cloning, leaving None behind.
+    pub fn take_params(&mut self) -> Option<Params> {
+        self.params.take()
+    }
+
+    /// Mutable access to the params, if present.
+    ///
+    /// Use [`Params::set`] to replace the value while keeping it an object or array.
+    pub fn params_mut(&mut self) -> Option<&mut Params> {
+        self.params.as_mut()
+    }
+
+    /// The "jsonrpc" version of the notification, "2.0" unless parsed with
+    /// `ParseOptions::forward_compatible`.
+    pub fn version(&self) -> &str {
+        self.jsonrpc.as_str()
+    }
+
+    /// Serializes the notification straight into `w`, without an intermediate `String`.
+    pub fn write_to<W: std::io::Write>(&self, w: W) -> Result<(), Error> {
+        serde_json::to_writer(w, self).map_err(|e| e.into())
+    }
+
+    /// Serializes the notification straight into `buf`, e.g. in a `tokio_util::codec::Encoder`.
+    #[cfg(feature = "bytes")]
+    pub fn encode_into(&self, buf: &mut bytes::BytesMut) -> Result<(), Error> {
+        self.write_to(bytes::BufMut::writer(buf))
+    }
+
+    /// Serializes the notification once into a cheaply clonable string, e.g. to hand the same
+    /// message to many subscribers.
+    pub fn to_shared_string(&self) -> Result<std::sync::Arc<str>, Error> {
+        Ok(serde_json::to_string(self)?.into())
+    }
+
+    /// Serializes the notification once and writes it to every sink.
+    ///
+    /// A failing sink doesn't stop the others from being written to, the first error is
+    /// returned once all sinks were tried.
+    pub fn broadcast<'a, W: std::io::Write + 'a>(
+        &self,
+        sinks: impl IntoIterator<Item = &'a mut W>,
+    ) -> Result<(), Error> {
+        let msg = serde_json::to_vec(self)?;
+        let mut result = Ok(());
+        for sink in sinks {
+            if let Err(e) = sink.write_all(&msg) {
+                result = result.and(Err(e.into()));
+            }
+        }
+        result
+    }
 }
 
 impl TryFrom<&str> for Notification {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value.as_bytes())?;
         serde_json::from_str(value).map_err(|e| e.into())
     }
 }
 
+impl TryFrom<&[u8]> for Notification {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value)?;
+        serde_json::from_slice(value).map_err(|e| e.into())
+    }
+}
+
 impl TryFrom<Notification> for String {
     type Error = Error;
 
@@ -35,6 +128,33 @@ impl TryFrom<Notification> for String {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Notification {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Notification {
+            jsonrpc: Version::V2_0,
+            method: u.arbitrary()?,
+            params: u.arbitrary()?,
+        })
+    }
+}
+
+/// A `proptest` strategy producing valid [`Notification`] objects.
+#[cfg(feature = "proptest")]
+pub fn arb_notification() -> impl proptest::strategy::Strategy<Value = Notification> {
+    use proptest::prelude::*;
+
+    (
+        any::<String>(),
+        proptest::option::of(crate::params::arb_params()),
+    )
+        .prop_map(|(method, params)| Notification {
+            jsonrpc: Version::V2_0,
+            method,
+            params,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +183,54 @@ mod tests {
         assert_eq!(req_obj.method, "notify_hello");
     }
 
+    #[test]
+    fn deserialize_bytes() {
+        let req = br#"{"jsonrpc": "2.0", "method": "update", "params": [1,2,3,4,5]}"#;
+        let req_obj = TryInto::<Notification>::try_into(&req[..]);
+        assert!(req_obj.is_ok());
+        assert_eq!(req_obj.unwrap().method, "update");
+
+        let req = b"{\"jsonrpc\": \"2.0\", \"method\": \"\xff\"}"; // invalid utf-8
+        let req_obj = TryInto::<Notification>::try_into(&req[..]);
+        assert!(req_obj.is_err());
+    }
+
+    #[test]
+    fn null_params() {
+        let obj = r#"{"jsonrpc":"2.0","method":"a","params":null}"#;
+        assert_eq!(TryInto::<Notification>::try_into(obj).unwrap().params, None);
+        let strict = ParseOptions {
+            strict_params: true,
+            ..ParseOptions::default()
+        };
+        assert!(Notification::parse_with(obj, &strict).is_err());
+
+        let obj = r#"{"jsonrpc":"2.0","method":"a"}"#;
+        assert_eq!(TryInto::<Notification>::try_into(obj).unwrap().params, None);
+        assert_eq!(
+            TryInto::<String>::try_into(Notification::builder().method("a").build()).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"a"}"#
+        );
+    }
+
+    #[test]
+    fn broadcast() {
+        let notif = Notification::builder().method("tick").build();
+        let shared = notif.to_shared_string().unwrap();
+        assert_eq!(&*shared, r#"{"jsonrpc":"2.0","method":"tick"}"#);
+
+        let mut sinks = vec![Vec::new(), Vec::new(), Vec::new()];
+        notif.broadcast(&mut sinks).unwrap();
+        for sink in &sinks {
+            assert_eq!(sink.as_slice(), shared.as_bytes());
+        }
+
+        let mut full = [0u8; 4];
+        let mut sinks: Vec<Box<dyn std::io::Write>> =
+            vec![Box::new(&mut full[..]), Box::new(Vec::new())];
+        assert!(matches!(notif.broadcast(&mut sinks), Err(Error::Io(_))));
+    }
+
     #[test]
     fn negative_serde_tests() {
         let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": 1}"#; // params is number
@@ -86,6 +254,58 @@ mod tests {
         assert!(req_obj.is_err());
     }
 
+    #[test]
+    fn builder_method_nonempty() {
+        let req = Notification::builder().method_nonempty("");
+        assert!(matches!(req, Err(Error::InvalidType(_))));
+
+        let req = Notification::builder().method_nonempty("test-notification");
+        assert_eq!(req.unwrap().build().method, "test-notification");
+    }
+
+    #[test]
+    fn builder_params_value() {
+        let params = TryInto::<Params>::try_into(r#"{"a":1}"#).unwrap();
+        let req = Notification::builder()
+            .method("test-method")
+            .params_value(params.clone())
+            .build();
+        assert_eq!(req.params, Some(params));
+    }
+
+    #[test]
+    fn write_to() {
+        let obj = Notification::builder()
+            .method("test-notification")
+            .params_str("[1,2]")
+            .unwrap()
+            .build();
+        let mut buf = Vec::new();
+        obj.write_to(&mut buf).unwrap();
+        #[cfg(feature = "bytes")]
+        {
+            let mut bytes = bytes::BytesMut::new();
+            obj.encode_into(&mut bytes).unwrap();
+            assert_eq!(bytes, buf);
+        }
+        assert_eq!(buf, TryInto::<String>::try_into(obj).unwrap().into_bytes());
+    }
+
+    #[test]
+    fn without_params() {
+        let req = Notification::builder().method("ping").build();
+        assert_eq!(req, Notification::new("ping"));
+        assert_eq!(req.params, None);
+
+        let req_str = TryInto::<String>::try_into(req.clone()).unwrap();
+        let new_req = TryInto::<Notification>::try_into(req_str.as_str());
+        assert_eq!(new_req.unwrap(), req);
+
+        let req = r#"{"jsonrpc": "2.0", "method": "ping"}"#;
+        let req_obj = TryInto::<Notification>::try_into(req);
+        assert_eq!(req_obj.unwrap(), Notification::new("ping"));
+    }
+
     #[test]
     fn builder() {
         let params = vec![10, 0];
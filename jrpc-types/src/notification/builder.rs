@@ -1,6 +1,6 @@
 //! This module implements a Builder class for the Request object.
 
-use crate::{error::Error, notification::Notification, params::Params};
+use crate::{error::Error, notification::Notification, params::Params, version::Version};
 
 // =======================
 // Type State Structs
@@ -10,6 +10,7 @@ pub struct Method(String);
 // =======================
 
 /// The Builder class for a Request object.
+#[must_use = "builders do nothing unless `build()` is called"]
 pub struct Builder<M> {
     method: M,
     params: Option<Params>,
@@ -31,6 +32,7 @@ impl Builder<MethodNone> {
 }
 
 impl<M> Builder<M> {
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params(self, p: serde_json::Value) -> Result<Builder<M>, Error> {
         Ok(Builder {
             method: self.method,
@@ -38,6 +40,7 @@ impl<M> Builder<M> {
         })
     }
 
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params_serialize<T: serde::Serialize>(self, p: T) -> Result<Builder<M>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         let params = Params::try_from(value)?;
@@ -47,6 +50,15 @@ impl<M> Builder<M> {
         })
     }
 
+    /// Sets already validated params, so no conversion can fail.
+    pub fn params_value(self, p: Params) -> Builder<M> {
+        Builder {
+            method: self.method,
+            params: Some(p),
+        }
+    }
+
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params_str(self, p: &str) -> Result<Builder<M>, Error> {
         let params = Params::try_from(p)?;
         Ok(Builder {
@@ -57,18 +69,37 @@ impl<M> Builder<M> {
 }
 
 impl Builder<MethodNone> {
-    pub fn method(self, m: &str) -> Builder<Method> {
+    pub fn method<T: Into<String>>(self, m: T) -> Builder<Method> {
+        let m = m.into();
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if m.is_empty() {
+            crate::diagnostics::discouraged(format_args!(
+                "building a notification with an empty method name"
+            ));
+        }
         Builder {
-            method: Method(m.to_string()),
+            method: Method(m),
             params: self.params,
         }
     }
+
+    /// Same as [`Builder::method`], but rejects an empty method name.
+    ///
+    /// The spec doesn't forbid empty method names, but in practice one is always a client bug.
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
+    pub fn method_nonempty<T: Into<String>>(self, m: T) -> Result<Builder<Method>, Error> {
+        let m = m.into();
+        if m.is_empty() {
+            return Err(Error::InvalidType("method must not be empty".to_string()));
+        }
+        Ok(self.method(m))
+    }
 }
 
 impl Builder<Method> {
     pub fn build(self) -> Notification {
         Notification {
-            jsonrpc: "2.0".to_string(),
+            jsonrpc: Version::V2_0,
             method: self.method.0,
             params: self.params,
         }
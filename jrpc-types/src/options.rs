@@ -0,0 +1,71 @@
+//! This module implements per-call parsing options, for peers that deviate from the spec.
+//!
+//! The options only apply to the `parse_with` call they're passed to, e.g.
+//! [`Request::parse_with`](crate::request::Request::parse_with). Parsing with `TryFrom` always
+//! uses the defaults, no matter what other code in the process does.
+
+use std::cell::Cell;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// Options for the `parse_with` functions. The defaults follow the spec.
+pub struct ParseOptions {
+    /// Rejects `"params": null` instead of reading it as absent params, since the spec says
+    /// params are omitted when absent.
+    pub strict_params: bool,
+    /// Accepts common deviations from non-conformant peers, such as an error code sent as a
+    /// string (e.g. "-32601"), scalar params (`"params": 5` is read as `[5]`), or a response
+    /// without "jsonrpc". This is NOT spec conformant.
+    pub lenient: bool,
+    /// Accepts any "jsonrpc" version "2.x" (e.g. "2.1" or "2.10") instead of only "2.0", in case
+    /// a future JSON-RPC 2.x arrives. The observed version is kept. This is speculative forward
+    /// compatibility, and NOT spec conformant.
+    pub forward_compatible: bool,
+}
+
+const DEFAULT: ParseOptions = ParseOptions {
+    strict_params: false,
+    lenient: false,
+    forward_compatible: false,
+};
+
+thread_local! {
+    static CURRENT: Cell<ParseOptions> = const { Cell::new(DEFAULT) };
+}
+
+/// The options of the `parse_with` call in progress on this thread, or the defaults.
+pub(crate) fn current() -> ParseOptions {
+    CURRENT.with(Cell::get)
+}
+
+/// Runs `f` with `options` as the current options, restoring the previous ones afterwards.
+pub(crate) fn with<T>(options: &ParseOptions, f: impl FnOnce() -> T) -> T {
+    struct Restore(ParseOptions);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(*options)));
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scoped_options() {
+        let lenient = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(current(), ParseOptions::default());
+        with(&lenient, || {
+            assert_eq!(current(), lenient);
+            with(&ParseOptions::default(), || assert!(!current().lenient));
+            assert_eq!(current(), lenient);
+        });
+        assert_eq!(current(), ParseOptions::default());
+    }
+}
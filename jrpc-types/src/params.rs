@@ -4,12 +4,254 @@ use std::ops::{Deref, DerefMut};
 
 use crate::error::Error;
 
-#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 /// This object implements the "params" field in JSON-RPC objects.
 ///
 /// "params" can only be a Structured Value (by-name, by-position)
 pub struct Params(pub serde_json::Value);
 
+impl Params {
+    /// Creates by-position params, which can't fail unlike `TryFrom<Value>`.
+    pub fn array(values: Vec<serde_json::Value>) -> Params {
+        Params(serde_json::Value::Array(values))
+    }
+
+    /// Creates by-name params, which can't fail unlike `TryFrom<Value>`.
+    pub fn object(map: serde_json::Map<String, serde_json::Value>) -> Params {
+        Params(serde_json::Value::Object(map))
+    }
+
+    /// Parses params like `TryFrom<&str>`, but rejects objects with duplicate keys at any depth.
+    ///
+    /// By default the last duplicate key silently wins, which can be exploited for parameter
+    /// smuggling when another parser in the chain keeps the first one instead.
+    pub fn try_from_str_strict(value: &str) -> Result<Self, Error> {
+        // UniqueKeys accepts any JSON value, so the only possible data error is a duplicate key.
+        let value = serde_json::from_str::<UniqueKeys>(value)
+            .map_err(|e| {
+                if e.is_data() {
+                    Error::InvalidType(e.to_string())
+                } else {
+                    Error::Serde(e)
+                }
+            })?
+            .0;
+        Params::try_from(value)
+    }
+
+    /// Compares params by meaning rather than by text.
+    ///
+    /// By-position (array) params are equal only if their elements are equal in the same order,
+    /// while by-name (object) params are equal if they have the same keys with equal values, in
+    /// any order. The same rules apply to nested values. This is also what `==` does.
+    pub fn semantically_eq(&self, other: &Params) -> bool {
+        // serde_json compares objects as maps, which ignores key order.
+        self.0 == other.0
+    }
+
+    /// Consumes the params, returning the underlying object or array value.
+    #[must_use]
+    pub fn into_inner(self) -> serde_json::Value {
+        self.0
+    }
+
+    /// Replaces the params value, rejecting anything that isn't an object or array.
+    pub fn set(&mut self, v: serde_json::Value) -> Result<(), Error> {
+        *self = Params::try_from(v)?;
+        Ok(())
+    }
+
+    /// Checks that there's at least one argument, for methods that can't be called without any.
+    ///
+    /// Fails with [`Error::InvalidType`] on an empty array or an object without keys.
+    pub fn require_non_empty(&self) -> Result<&Params, Error> {
+        let empty = match &self.0 {
+            serde_json::Value::Array(a) => a.is_empty(),
+            serde_json::Value::Object(o) => o.is_empty(),
+            _ => false,
+        };
+        if empty {
+            return Err(Error::InvalidType("params must not be empty".to_string()));
+        }
+        Ok(self)
+    }
+
+    /// Checks that every integer, at any depth, fits in the JavaScript safe integer range
+    /// (±(2^53 - 1)), so the params survive a round trip through a JavaScript peer.
+    pub fn check_safe_integers(&self) -> Result<(), Error> {
+        const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+        fn check(value: &serde_json::Value) -> Result<(), Error> {
+            match value {
+                serde_json::Value::Number(n) => {
+                    let safe = match (n.as_u64(), n.as_i64()) {
+                        (Some(v), _) => v <= MAX_SAFE_INTEGER,
+                        (None, Some(v)) => v.unsigned_abs() <= MAX_SAFE_INTEGER,
+                        (None, None) => true,
+                    };
+                    if !safe {
+                        return Err(Error::InvalidType(format!(
+                            "params integer {n} exceeds the JavaScript safe integer range"
+                        )));
+                    }
+                    Ok(())
+                }
+                serde_json::Value::Array(a) => a.iter().try_for_each(check),
+                serde_json::Value::Object(o) => o.values().try_for_each(check),
+                _ => Ok(()),
+            }
+        }
+        check(&self.0)
+    }
+
+    /// The by-name params, or None for by-position params.
+    pub fn as_object(&self) -> Option<&serde_json::Map<String, serde_json::Value>> {
+        self.0.as_object()
+    }
+
+    /// The by-position params, or None for by-name params.
+    pub fn as_array(&self) -> Option<&Vec<serde_json::Value>> {
+        self.0.as_array()
+    }
+
+    /// Looks up a nested value by JSON Pointer (RFC 6901), e.g. `"/user/address/zip"` or `"/0"`.
+    pub fn pointer(&self, pointer: &str) -> Option<&serde_json::Value> {
+        self.0.pointer(pointer)
+    }
+
+    /// Looks up a nested value by JSON Pointer and deserializes it into `T`.
+    ///
+    /// Fails with [`Error::InvalidType`] if nothing is found at the pointer, or with
+    /// [`Error::ParamPath`] if the value found there has the wrong type.
+    pub fn pointer_as<T: serde::de::DeserializeOwned>(&self, pointer: &str) -> Result<T, Error> {
+        let value = self
+            .pointer(pointer)
+            .ok_or_else(|| Error::InvalidType(format!("no params value at {pointer}")))?;
+        // Render the path like `deserialize_at` does, with `[n]` for array indices.
+        let mut path = "params".to_string();
+        let mut parent = &self.0;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            let child = match parent {
+                serde_json::Value::Array(a) => {
+                    path.push_str(&format!("[{token}]"));
+                    token.parse::<usize>().ok().and_then(|i| a.get(i))
+                }
+                _ => {
+                    path.push('.');
+                    path.push_str(&token);
+                    parent.get(&token)
+                }
+            };
+            // The pointer resolved above, so every step exists.
+            parent = child.unwrap_or(parent);
+        }
+        deserialize_at(value, path)
+    }
+
+    /// Deserializes by-position params into a tuple, e.g. `[1, "a"]` into `(i64, String)`.
+    ///
+    /// Calling this on by-name params is an error.
+    pub fn as_tuple<T: serde::de::DeserializeOwned>(&self) -> Result<T, Error> {
+        if !self.0.is_array() {
+            return Err(Error::InvalidType(
+                "cannot deserialize by-name params into a tuple".to_string(),
+            ));
+        }
+        deserialize_at(&self.0, "params".to_string())
+    }
+
+    /// Deserializes by-name params whose values all share a type into a map, e.g.
+    /// `{"a": 1, "b": 2}` into `HashMap<String, i64>`.
+    ///
+    /// Calling this on by-position params is an error.
+    pub fn as_map<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<std::collections::HashMap<String, T>, Error> {
+        if !self.0.is_object() {
+            return Err(Error::InvalidType(
+                "cannot deserialize by-position params into a map".to_string(),
+            ));
+        }
+        deserialize_at(&self.0, "params".to_string())
+    }
+
+    /// Deserializes the elements of by-position params read from `reader` one at a time and
+    /// passes each to `f`, so a huge array is never materialized as a whole.
+    ///
+    /// `reader` must yield only the params array, not the whole request, e.g. the bytes of a
+    /// `Box<RawValue>` "params" field kept with the `raw` feature. Stops at the first element
+    /// that fails to deserialize or for which `f` fails, and returns that error.
+    pub fn stream_positional<T, R, F>(reader: R, mut f: F) -> Result<(), Error>
+    where
+        T: serde::de::DeserializeOwned,
+        R: std::io::Read,
+        F: FnMut(T) -> Result<(), Error>,
+    {
+        struct PositionalVisitor<'a, T, F> {
+            f: &'a mut F,
+            err: &'a mut Option<Error>,
+            element: std::marker::PhantomData<T>,
+        }
+        impl<'de, T, F> serde::de::Visitor<'de> for PositionalVisitor<'_, T, F>
+        where
+            T: serde::de::DeserializeOwned,
+            F: FnMut(T) -> Result<(), Error>,
+        {
+            type Value = ();
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("by-position params")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                while let Some(element) = seq.next_element::<T>()? {
+                    if let Err(e) = (self.f)(element) {
+                        *self.err = Some(e);
+                        return Err(serde::de::Error::custom("aborted by the element handler"));
+                    }
+                }
+                Ok(())
+            }
+        }
+
+        let mut err = None;
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+        let visitor = PositionalVisitor {
+            f: &mut f,
+            err: &mut err,
+            element: std::marker::PhantomData,
+        };
+        match serde::Deserializer::deserialize_seq(&mut deserializer, visitor) {
+            Ok(()) => deserializer.end().map_err(Error::from),
+            Err(e) => Err(err.unwrap_or(e.into())),
+        }
+    }
+}
+
+/// Deserializes `value`, found at `path`, into `T`. On failure, the path is extended to the
+/// offending value in an [`Error::ParamPath`].
+fn deserialize_at<T: serde::de::DeserializeOwned>(
+    value: &serde_json::Value,
+    mut path: String,
+) -> Result<T, Error> {
+    serde_path_to_error::deserialize(value).map_err(|e| {
+        for segment in e.path().iter() {
+            if !matches!(segment, serde_path_to_error::Segment::Seq { .. }) {
+                path.push('.');
+            }
+            path.push_str(&segment.to_string());
+        }
+        Error::ParamPath {
+            path,
+            detail: e.into_inner().to_string(),
+        }
+    })
+}
+
 impl Deref for Params {
     type Target = serde_json::Value;
 
@@ -18,6 +260,83 @@ impl Deref for Params {
     }
 }
 
+/// Hashes params canonically, so params that are `==` (e.g. objects with the same keys in a
+/// different order) hash the same.
+impl std::hash::Hash for Params {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        hash_value(&self.0, state);
+    }
+}
+
+/// Hashes a value canonically, consistent with `==`, which ignores the order of object keys.
+pub(crate) fn hash_value<H: std::hash::Hasher>(value: &serde_json::Value, state: &mut H) {
+    use std::hash::Hash;
+
+    std::mem::discriminant(value).hash(state);
+    match value {
+        serde_json::Value::Null => {}
+        serde_json::Value::Bool(v) => v.hash(state),
+        serde_json::Value::Number(v) => v.to_string().hash(state),
+        serde_json::Value::String(v) => v.hash(state),
+        serde_json::Value::Array(a) => {
+            a.len().hash(state);
+            a.iter().for_each(|v| hash_value(v, state));
+        }
+        serde_json::Value::Object(o) => {
+            let mut entries: Vec<_> = o.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            entries.len().hash(state);
+            for (k, v) in entries {
+                k.hash(state);
+                hash_value(v, state);
+            }
+        }
+    }
+}
+
+/// Writes a value as compact JSON with the keys of every object sorted, see
+/// `Request::to_canonical_string`.
+pub(crate) fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Array(a) => {
+            out.push('[');
+            for (i, v) in a.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(v, out);
+            }
+            out.push(']');
+        }
+        serde_json::Value::Object(o) => {
+            let mut entries: Vec<_> = o.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (k, v)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::from(k.as_str()).to_string());
+                out.push(':');
+                write_canonical(v, out);
+            }
+            out.push('}');
+        }
+        // Scalars already have a single compact form.
+        v => out.push_str(&v.to_string()),
+    }
+}
+
+impl AsRef<serde_json::Value> for Params {
+    fn as_ref(&self) -> &serde_json::Value {
+        &self.0
+    }
+}
+
+/// Allows editing the structured value in place, e.g. `params["key"] = json!(1)`.
+///
+/// Replacing the whole value through this can break the object/array invariant, use
+/// [`Params::set`] for that instead.
 impl DerefMut for Params {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
@@ -39,6 +358,107 @@ impl<'de> serde::Deserialize<'de> for Params {
     }
 }
 
+/// A JSON value whose objects are guaranteed to not contain duplicate keys.
+struct UniqueKeys(serde_json::Value);
+
+impl<'de> serde::Deserialize<'de> for UniqueKeys {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct UniqueKeysVisitor;
+        impl<'de> serde::de::Visitor<'de> for UniqueKeysVisitor {
+            type Value = UniqueKeys;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("any valid JSON value")
+            }
+
+            fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(v.into()))
+            }
+
+            fn visit_unit<E>(self) -> Result<Self::Value, E> {
+                Ok(UniqueKeys(serde_json::Value::Null))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut values = Vec::new();
+                while let Some(UniqueKeys(v)) = seq.next_element()? {
+                    values.push(v);
+                }
+                Ok(UniqueKeys(serde_json::Value::Array(values)))
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let mut values = serde_json::Map::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    if values.contains_key(&key) {
+                        return Err(serde::de::Error::custom(format!(
+                            "duplicate key \"{key}\" in params"
+                        )));
+                    }
+                    let UniqueKeys(v) = map.next_value()?;
+                    values.insert(key, v);
+                }
+                Ok(UniqueKeys(serde_json::Value::Object(values)))
+            }
+        }
+        deserializer.deserialize_any(UniqueKeysVisitor)
+    }
+}
+
+/// Deserializes the optional "params" field of requests and notifications.
+///
+/// An explicit `"params": null` is read as absent params, unless
+/// [`ParseOptions::strict_params`](crate::options::ParseOptions::strict_params) is set.
+pub(crate) fn deserialize_optional<'de, D>(deserializer: D) -> Result<Option<Params>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let options = crate::options::current();
+    match <serde_json::Value as serde::Deserialize>::deserialize(deserializer)? {
+        serde_json::Value::Null if options.strict_params => Err(serde::de::Error::custom(
+            r#"absent "params" must be omitted, not null"#,
+        )),
+        serde_json::Value::Null => Ok(None),
+        value @ (serde_json::Value::Object(_) | serde_json::Value::Array(_)) => {
+            Ok(Some(Params(value)))
+        }
+        // Simplistic clients send a single scalar meaning a one-element positional param.
+        value if options.lenient => Ok(Some(Params(serde_json::Value::Array(vec![value])))),
+        _ => Err(serde::de::Error::custom(
+            r#""params" must be a JSON object or array"#,
+        )),
+    }
+}
+
 impl TryFrom<&str> for Params {
     type Error = Error;
 
@@ -68,6 +488,118 @@ impl TryFrom<serde_json::Value> for Params {
     }
 }
 
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Params {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Params".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        // The custom deserializer only accepts structured values, so the schema has to say so.
+        schemars::json_schema!({
+            "description": "JSON-RPC \"params\" are either by-name (Object) or by-position (Array).",
+            "type": ["object", "array"]
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+pub(crate) const ARBITRARY_DEPTH: usize = 3;
+
+#[cfg(feature = "arbitrary")]
+const ARBITRARY_MAX_LEN: usize = 8;
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Params {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // The top level is always structured, nested values can be anything.
+        let value = if u.arbitrary()? {
+            arbitrary_array(u, ARBITRARY_DEPTH)?
+        } else {
+            arbitrary_object(u, ARBITRARY_DEPTH)?
+        };
+        Ok(Params(value))
+    }
+}
+
+/// Generates an arbitrary JSON value, nesting at most `depth` levels of arrays/objects.
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_value(
+    u: &mut arbitrary::Unstructured<'_>,
+    depth: usize,
+) -> arbitrary::Result<serde_json::Value> {
+    let max = if depth == 0 { 4 } else { 6 };
+    Ok(match u.int_in_range(0..=max)? {
+        0 => serde_json::Value::Null,
+        1 => serde_json::Value::Bool(u.arbitrary()?),
+        2 => serde_json::Value::from(u.arbitrary::<i64>()?),
+        // Dyadic fractions keep the decimal form short, so they parse back bit-exact.
+        3 => serde_json::Value::from(f64::from(u.arbitrary::<i32>()?) / 256.0),
+        4 => serde_json::Value::String(u.arbitrary()?),
+        5 => arbitrary_array(u, depth - 1)?,
+        _ => arbitrary_object(u, depth - 1)?,
+    })
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_array(
+    u: &mut arbitrary::Unstructured<'_>,
+    depth: usize,
+) -> arbitrary::Result<serde_json::Value> {
+    let len = u.int_in_range(0..=ARBITRARY_MAX_LEN)?;
+    let values = (0..len)
+        .map(|_| arbitrary_value(u, depth))
+        .collect::<arbitrary::Result<_>>()?;
+    Ok(serde_json::Value::Array(values))
+}
+
+#[cfg(feature = "arbitrary")]
+fn arbitrary_object(
+    u: &mut arbitrary::Unstructured<'_>,
+    depth: usize,
+) -> arbitrary::Result<serde_json::Value> {
+    let len = u.int_in_range(0..=ARBITRARY_MAX_LEN)?;
+    let mut map = serde_json::Map::new();
+    for _ in 0..len {
+        map.insert(u.arbitrary()?, arbitrary_value(u, depth)?);
+    }
+    Ok(serde_json::Value::Object(map))
+}
+
+/// A `proptest` strategy producing any JSON value, nested up to a few levels deep.
+#[cfg(feature = "proptest")]
+pub fn arb_value() -> impl proptest::strategy::Strategy<Value = serde_json::Value> {
+    use proptest::prelude::*;
+
+    let leaf = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::Bool),
+        any::<i64>().prop_map(serde_json::Value::from),
+        // Dyadic fractions keep the decimal form short, so they parse back bit-exact.
+        any::<i32>().prop_map(|v| serde_json::Value::from(f64::from(v) / 256.0)),
+        any::<String>().prop_map(serde_json::Value::String),
+    ];
+    leaf.prop_recursive(3, 64, 8, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..8).prop_map(serde_json::Value::Array),
+            prop::collection::btree_map(any::<String>(), inner, 0..8)
+                .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+        ]
+    })
+}
+
+/// A `proptest` strategy producing valid [`Params`], either by-position or by-name.
+#[cfg(feature = "proptest")]
+pub fn arb_params() -> impl proptest::strategy::Strategy<Value = Params> {
+    use proptest::prelude::*;
+
+    prop_oneof![
+        prop::collection::vec(arb_value(), 0..8).prop_map(|v| Params(serde_json::Value::Array(v))),
+        prop::collection::btree_map(any::<String>(), arb_value(), 0..8)
+            .prop_map(|m| Params(serde_json::Value::Object(m.into_iter().collect()))),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,6 +618,275 @@ mod tests {
         assert!(params_obj.is_ok());
     }
 
+    #[test]
+    fn params_constructors() {
+        let params = Params::array(vec![1.into(), "a".into()]);
+        assert!(params.is_array());
+        assert_eq!(params, TryInto::<Params>::try_into(r#"[1,"a"]"#).unwrap());
+
+        let mut map = serde_json::Map::new();
+        map.insert("a".to_string(), 1.into());
+        let params = Params::object(map);
+        assert!(params.is_object());
+        assert_eq!(params, TryInto::<Params>::try_into(r#"{"a":1}"#).unwrap());
+    }
+
+    #[test]
+    fn params_views() {
+        let params = TryInto::<Params>::try_into(r#"{"a":1}"#).unwrap();
+        assert_eq!(params.as_object().unwrap()["a"], 1);
+        assert_eq!(params.as_array(), None);
+
+        let params = TryInto::<Params>::try_into("[1,2]").unwrap();
+        assert_eq!(params.as_array().unwrap().len(), 2);
+        assert_eq!(params.as_object(), None);
+    }
+
+    #[test]
+    fn stream_positional() {
+        let mut sum = 0;
+        Params::stream_positional("[1, 2, 3]".as_bytes(), |v: i64| {
+            sum += v;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(sum, 6);
+
+        let mut seen = 0;
+        let err = Params::stream_positional("[1, 2, 3]".as_bytes(), |v: i64| {
+            seen += 1;
+            match v {
+                2 => Err(Error::LimitExceeded("too many".to_string())),
+                _ => Ok(()),
+            }
+        })
+        .unwrap_err();
+        assert!(matches!(err, Error::LimitExceeded(_)));
+        assert_eq!(seen, 2);
+
+        for input in [r#"{"a":1}"#, r#"[1, "a"]"#, "[1] 2"] {
+            assert!(matches!(
+                Params::stream_positional(input.as_bytes(), |_: i64| Ok(())),
+                Err(Error::Serde(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn param_path() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct User {
+            age: u8,
+        }
+
+        let params = TryInto::<Params>::try_into(r#"{"user":{"age":"old"}}"#).unwrap();
+        let err = params.pointer_as::<User>("/user").unwrap_err();
+        assert!(matches!(&err, Error::ParamPath { path, .. } if path == "params.user.age"));
+        assert!(
+            err.to_string()
+                .starts_with("invalid params.user.age: invalid type")
+        );
+    }
+
+    #[test]
+    fn params_as_tuple() {
+        let params = TryInto::<Params>::try_into(r#"[42,"hi"]"#).unwrap();
+        let (x, y): (i64, String) = params.as_tuple().unwrap();
+        assert_eq!(x, 42);
+        assert_eq!(y, "hi");
+        assert!(params.as_tuple::<(i64, String, bool)>().is_err());
+        assert!(matches!(
+            params.as_tuple::<(String, i64)>(),
+            Err(Error::ParamPath { path, .. }) if path == "params[0]"
+        ));
+
+        let params = TryInto::<Params>::try_into(r#"{"x":42,"y":"hi"}"#).unwrap();
+        assert!(matches!(
+            params.as_tuple::<(i64, String)>(),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn params_as_map() {
+        let params = TryInto::<Params>::try_into(r#"{"a":1,"b":2}"#).unwrap();
+        let map = params.as_map::<i64>().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["a"], 1);
+        assert_eq!(map["b"], 2);
+        assert!(matches!(
+            params.as_map::<String>(),
+            Err(Error::ParamPath { path, .. }) if path.starts_with("params.")
+        ));
+
+        let params = TryInto::<Params>::try_into(r#"[1,2]"#).unwrap();
+        assert!(matches!(params.as_map::<i64>(), Err(Error::InvalidType(_))));
+    }
+
+    #[test]
+    fn params_strict_duplicate_keys() {
+        let params = r#"{"a":1,"b":{"c":[1,2.5,"x",null,true]}}"#;
+        let params_obj = Params::try_from_str_strict(params);
+        assert_eq!(
+            params_obj.unwrap(),
+            TryInto::<Params>::try_into(params).unwrap()
+        );
+
+        let params = r#"{"a":1,"a":2}"#;
+        assert!(TryInto::<Params>::try_into(params).is_ok());
+        let params_obj = Params::try_from_str_strict(params);
+        assert!(matches!(params_obj, Err(Error::InvalidType(_))));
+
+        let params = r#"[{"b":{"a":1,"a":2}}]"#;
+        let params_obj = Params::try_from_str_strict(params);
+        assert!(matches!(params_obj, Err(Error::InvalidType(_))));
+
+        let params = r#"{"a":1"#;
+        let params_obj = Params::try_from_str_strict(params);
+        assert!(matches!(params_obj, Err(Error::Serde(_))));
+
+        let params = r#"12"#;
+        assert!(Params::try_from_str_strict(params).is_err());
+    }
+
+    #[test]
+    fn params_pointer() {
+        let params =
+            TryInto::<Params>::try_into(r#"{"user":{"address":{"zip":"12345"},"tags":[1,2]}}"#)
+                .unwrap();
+        assert_eq!(
+            params.pointer("/user/address/zip"),
+            Some(&serde_json::json!("12345"))
+        );
+        assert_eq!(params.pointer("/user/tags/1"), Some(&serde_json::json!(2)));
+        assert_eq!(params.pointer("/user/name"), None);
+
+        assert_eq!(
+            params.pointer_as::<String>("/user/address/zip").unwrap(),
+            "12345"
+        );
+        assert_eq!(
+            params.pointer_as::<Vec<u8>>("/user/tags").unwrap(),
+            vec![1, 2]
+        );
+        assert!(matches!(
+            params.pointer_as::<i64>("/user/address/zip"),
+            Err(Error::ParamPath { path, .. }) if path == "params.user.address.zip"
+        ));
+        assert!(matches!(
+            params.pointer_as::<Vec<bool>>("/user/tags"),
+            Err(Error::ParamPath { path, .. }) if path == "params.user.tags[0]"
+        ));
+        assert!(matches!(
+            params.pointer_as::<String>("/user/tags/1"),
+            Err(Error::ParamPath { path, .. }) if path == "params.user.tags[1]"
+        ));
+        assert!(matches!(
+            params.pointer_as::<String>("/user/name"),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn params_set() {
+        let mut params = TryInto::<Params>::try_into(r#"[1,2]"#).unwrap();
+        assert!(params.set(serde_json::json!({"a": 1})).is_ok());
+        assert_eq!(*params, serde_json::json!({"a": 1}));
+
+        assert!(params.set(serde_json::json!(5)).is_err());
+        assert!(params.set(serde_json::Value::Null).is_err());
+        assert_eq!(*params, serde_json::json!({"a": 1}));
+    }
+
+    #[test]
+    fn params_serialize_bare() {
+        let params = TryInto::<Params>::try_into(r#"{"a":[1,2]}"#).unwrap();
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"a":[1,2]}"#);
+        assert_eq!(
+            serde_json::to_value(&params).unwrap(),
+            serde_json::json!({"a": [1, 2]})
+        );
+
+        let params = TryInto::<Params>::try_into(r#"[1,"a"]"#).unwrap();
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"[1,"a"]"#);
+        assert_eq!(params.as_ref(), &serde_json::json!([1, "a"]));
+        assert_eq!(params.into_inner(), serde_json::json!([1, "a"]));
+    }
+
+    #[test]
+    fn params_require_non_empty() {
+        let params = TryInto::<Params>::try_into(r#"[]"#).unwrap();
+        assert!(matches!(
+            params.require_non_empty(),
+            Err(Error::InvalidType(_))
+        ));
+
+        let params = TryInto::<Params>::try_into(r#"{}"#).unwrap();
+        assert!(matches!(
+            params.require_non_empty(),
+            Err(Error::InvalidType(_))
+        ));
+
+        let params = TryInto::<Params>::try_into(r#"[0]"#).unwrap();
+        assert_eq!(params.require_non_empty().unwrap(), &params);
+        let params = TryInto::<Params>::try_into(r#"{"a":null}"#).unwrap();
+        assert_eq!(params.require_non_empty().unwrap(), &params);
+    }
+
+    #[test]
+    fn params_check_safe_integers() {
+        let params =
+            TryInto::<Params>::try_into(r#"[9007199254740991,-9007199254740991,1.5e300]"#).unwrap();
+        assert!(params.check_safe_integers().is_ok());
+
+        let params = TryInto::<Params>::try_into(r#"[9007199254740992]"#).unwrap();
+        assert!(matches!(
+            params.check_safe_integers(),
+            Err(Error::InvalidType(_))
+        ));
+
+        let params = TryInto::<Params>::try_into(r#"{"a":{"b":[1,-9007199254740992]}}"#).unwrap();
+        assert!(matches!(
+            params.check_safe_integers(),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn params_hash() {
+        use std::hash::{BuildHasher, RandomState};
+
+        let hasher = RandomState::new();
+        let a = TryInto::<Params>::try_into(r#"{"a":1,"b":{"c":[1,2],"d":true}}"#).unwrap();
+        let b = TryInto::<Params>::try_into(r#"{"b":{"d":true,"c":[1,2]},"a":1}"#).unwrap();
+        assert_eq!(hasher.hash_one(&a), hasher.hash_one(&b));
+
+        let b = TryInto::<Params>::try_into(r#"{"b":{"d":true,"c":[2,1]},"a":1}"#).unwrap();
+        assert_ne!(hasher.hash_one(&a), hasher.hash_one(&b));
+
+        let a = TryInto::<Params>::try_into(r#"[[1],2]"#).unwrap();
+        let b = TryInto::<Params>::try_into(r#"[[1,2]]"#).unwrap();
+        assert_ne!(hasher.hash_one(&a), hasher.hash_one(&b));
+    }
+
+    #[test]
+    fn params_semantically_eq() {
+        let a = TryInto::<Params>::try_into(r#"{"a":1,"b":{"c":[1,2],"d":true}}"#).unwrap();
+        let b = TryInto::<Params>::try_into(r#"{"b":{"d":true,"c":[1,2]},"a":1}"#).unwrap();
+        assert!(a.semantically_eq(&b));
+        assert_eq!(a, b);
+
+        let b = TryInto::<Params>::try_into(r#"{"b":{"d":true,"c":[2,1]},"a":1}"#).unwrap();
+        assert!(!a.semantically_eq(&b));
+        assert_ne!(a, b);
+
+        let a = TryInto::<Params>::try_into(r#"[1,2]"#).unwrap();
+        let b = TryInto::<Params>::try_into(r#"[2,1]"#).unwrap();
+        assert!(!a.semantically_eq(&b));
+        assert_ne!(a, b);
+    }
+
     #[test]
     fn params_negative_tests() {
         let params = r#"12"#;
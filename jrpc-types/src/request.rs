@@ -1,33 +1,265 @@
 //! This module implements the request JSON-RPC object.
 
-use crate::{error::Error, id::Id, params::Params};
+use crate::{
+    error::Error, id::Id, limits::Limits, options::ParseOptions, params::Params,
+    response::Response, version::Version,
+};
 
 pub mod builder;
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The JSON-RPC Request Object
 pub struct Request {
-    #[serde(deserialize_with = "crate::version::version_deserialize")]
-    jsonrpc: String,
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Jsonrpc", alias = "JSONRPC")
+    )]
+    jsonrpc: Version,
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Method", alias = "METHOD")
+    )]
     pub method: String,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "crate::params::deserialize_optional"
+    )]
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Params", alias = "PARAMS")
+    )]
     pub params: Option<Params>,
+    #[cfg_attr(feature = "case-insensitive", serde(alias = "Id", alias = "ID"))]
     pub id: Id,
+    /// A "meta" object used by some frameworks. This is an extension beyond the spec, only
+    /// serialized when present.
+    #[cfg(feature = "meta")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
+}
+
+impl std::hash::Hash for Request {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.jsonrpc.hash(state);
+        self.method.hash(state);
+        self.params.hash(state);
+        self.id.hash(state);
+        #[cfg(feature = "meta")]
+        if let Some(meta) = &self.meta {
+            crate::params::hash_value(meta, state);
+        }
+    }
 }
 
 impl Request {
     pub fn builder() -> builder::Builder<builder::MethodNone, builder::IdNone> {
         builder::Builder::new()
     }
+
+    /// Parses a request from untrusted input, rejecting it with [`Error::LimitExceeded`] if it's
+    /// bigger or more deeply nested than `limits` allow, before it's deserialized.
+    pub fn try_from_limited(value: &str, limits: &Limits) -> Result<Request, Error> {
+        limits.check(value.as_bytes())?;
+        Request::try_from(value)
+    }
+
+    /// Parses a request with `options`, e.g. to accept the deviations of a non-conformant peer.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<Request, Error> {
+        crate::options::with(options, || Request::try_from(value))
+    }
+
+    /// Serializes the request in canonical form, e.g. to hash or sign it.
+    ///
+    /// The canonical form is compact JSON without any whitespace, with the keys of every object
+    /// (including the request itself) sorted by their UTF-8 bytes. Strings use the minimal
+    /// escaping, and numbers their shortest round-trip form, so `1.50` becomes `1.5` but `1.0`
+    /// and `1` stay distinct. Absent params are omitted.
+    pub fn to_canonical_string(&self) -> Result<String, Error> {
+        let value = serde_json::to_value(self)?;
+        let mut out = String::new();
+        crate::params::write_canonical(&value, &mut out);
+        Ok(out)
+    }
+
+    /// Builds a request from its parts, the dual of [`Request::into_parts`].
+    ///
+    /// `Params` can only hold an object or array, so the result is always a valid request.
+    #[must_use]
+    pub fn from_parts<M: Into<String>, I: Into<Id>>(
+        method: M,
+        params: Option<Params>,
+        id: I,
+    ) -> Request {
+        Request {
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
+            method: method.into(),
+            params,
+            id: id.into(),
+        }
+    }
+
+    /// Splits the request into its method, params, and id, e.g. to rebuild it with
+    /// [`Request::from_parts`] after changing one of them.
+    #[must_use]
+    pub fn into_parts(self) -> (String, Option<Params>, Id) {
+        (self.method, self.params, self.id)
+    }
+
+    /// Wraps the request for logging, with a `Debug` output that hides the params.
+    pub fn debug_redacted(&self) -> Redacted<'_> {
+        Redacted(self)
+    }
+
+    /// Builds the -32601 "Method not found" error response for this request.
+    pub fn method_not_found_response(&self) -> Response {
+        Response::builder()
+            .id(self)
+            .error()
+            .method_not_found()
+            .build()
+    }
+
+    /// Builds the -32602 "Invalid params" error response for this request, with optional `data`.
+    pub fn invalid_params_response(&self, data: Option<serde_json::Value>) -> Response {
+        let builder = Response::builder().id(self).error().invalid_params();
+        match data {
+            Some(data) => builder.data(data).build(),
+            None => builder.build(),
+        }
+    }
+
+    /// Moves the params out without cloning, leaving None behind.
+    pub fn take_params(&mut self) -> Option<Params> {
+        self.params.take()
+    }
+
+    /// Mutable access to the params, if present.
+    ///
+    /// Use [`Params::set`] to replace the value while keeping it an object or array.
+    pub fn params_mut(&mut self) -> Option<&mut Params> {
+        self.params.as_mut()
+    }
+
+    /// The "jsonrpc" version of the request, "2.0" unless parsed with
+    /// `ParseOptions::forward_compatible`.
+    pub fn version(&self) -> &str {
+        self.jsonrpc.as_str()
+    }
+
+    /// Serializes the request straight into `w`, without an intermediate `String`.
+    pub fn write_to<W: std::io::Write>(&self, w: W) -> Result<(), Error> {
+        serde_json::to_writer(w, self).map_err(|e| e.into())
+    }
+
+    /// Serializes the request straight into `buf`, e.g. in a `tokio_util::codec::Encoder`.
+    #[cfg(feature = "bytes")]
+    pub fn encode_into(&self, buf: &mut bytes::BytesMut) -> Result<(), Error> {
+        self.write_to(bytes::BufMut::writer(buf))
+    }
+
+    /// Returns the length in bytes of the serialized request, without allocating it, e.g. for
+    /// a Content-Length header.
+    pub fn serialized_len(&self) -> Result<usize, Error> {
+        struct Counter(usize);
+
+        impl std::io::Write for Counter {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0 += buf.len();
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut counter = Counter(0);
+        self.write_to(&mut counter)?;
+        Ok(counter.0)
+    }
+
+    /// Returns a reference to the request id.
+    pub fn id_ref(&self) -> &Id {
+        &self.id
+    }
+
+    /// Peeks at a Number id, returning None for any other id type.
+    pub fn id_as_i64(&self) -> Option<i64> {
+        self.id.as_i64()
+    }
+
+    /// Peeks at a String id, returning None for any other id type.
+    pub fn id_as_str(&self) -> Option<&str> {
+        self.id.as_str()
+    }
+}
+
+/// Prints the request envelope (version, method, id) with `Debug`, hiding params that may carry
+/// secrets such as passwords or tokens.
+pub struct Redacted<'a>(pub &'a Request);
+
+impl std::fmt::Debug for Redacted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let params = self.0.params.as_ref().map(|_| format_args!("<redacted>"));
+        f.debug_struct("Request")
+            .field("jsonrpc", &self.0.jsonrpc.as_str())
+            .field("method", &self.0.method)
+            .field("params", &params)
+            .field("id", &self.0.id)
+            .finish()
+    }
+}
+
+/// A request carrying per-request context (e.g. the remote address or auth principal) that is
+/// never serialized, only the request itself is.
+#[derive(Debug, Clone)]
+pub struct WithContext<C> {
+    pub request: Request,
+    pub context: C,
+}
+
+impl<C> WithContext<C> {
+    pub fn new(request: Request, context: C) -> Self {
+        WithContext { request, context }
+    }
+}
+
+impl<C> std::ops::Deref for WithContext<C> {
+    type Target = Request;
+
+    fn deref(&self) -> &Request {
+        &self.request
+    }
+}
+
+impl<C> serde::Serialize for WithContext<C> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.request.serialize(serializer)
+    }
 }
 
 impl TryFrom<&str> for Request {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value.as_bytes())?;
         serde_json::from_str(value).map_err(|e| e.into())
     }
 }
 
+impl TryFrom<&[u8]> for Request {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value)?;
+        serde_json::from_slice(value).map_err(|e| e.into())
+    }
+}
+
 impl TryFrom<Request> for String {
     type Error = Error;
 
@@ -44,6 +276,40 @@ impl From<&Request> for Id {
     }
 }
 
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Request {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Request {
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
+            method: u.arbitrary()?,
+            params: u.arbitrary()?,
+            id: u.arbitrary()?,
+        })
+    }
+}
+
+/// A `proptest` strategy producing valid [`Request`] objects.
+#[cfg(feature = "proptest")]
+pub fn arb_request() -> impl proptest::strategy::Strategy<Value = Request> {
+    use proptest::prelude::*;
+
+    (
+        any::<String>(),
+        proptest::option::of(crate::params::arb_params()),
+        crate::id::arb_id(),
+    )
+        .prop_map(|(method, params, id)| Request {
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
+            method,
+            params,
+            id,
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,6 +321,7 @@ mod tests {
         assert!(req_obj.is_ok());
         let req_obj = req_obj.unwrap();
         assert_eq!(req_obj.jsonrpc, "2.0");
+        assert_eq!(req_obj.version(), "2.0");
         assert_eq!(req_obj.method, "subtract");
 
         let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [23, 42], "id": 2}"#;
@@ -95,19 +362,83 @@ mod tests {
     }
 
     #[test]
-    fn negative_serde_tests() {
-        let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23]}"#; // no id
+    fn deserialize_bytes() {
+        let req = br#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id": 1}"#;
+        let req_obj = TryInto::<Request>::try_into(&req[..]);
+        assert!(req_obj.is_ok());
+        assert_eq!(req_obj.unwrap().method, "subtract");
+
+        let req = b"{\"jsonrpc\": \"2.0\", \"method\": \"\xff\", \"id\": 1}"; // invalid utf-8
+        let req_obj = TryInto::<Request>::try_into(&req[..]);
+        assert!(req_obj.is_err());
+    }
+
+    #[test]
+    fn capitalized_field_names() {
+        let req = r#"{"Jsonrpc": "2.0", "Method": "subtract", "Params": [42, 23], "Id": 1}"#;
         let req_obj = TryInto::<Request>::try_into(req);
+        #[cfg(feature = "case-insensitive")]
+        assert_eq!(req_obj.unwrap().method, "subtract");
+        #[cfg(not(feature = "case-insensitive"))]
         assert!(req_obj.is_err());
 
-        let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id":{}}"#; // id is obj
+        let req = r#"{"JSONRPC": "2.0", "METHOD": "subtract", "ID": 1}"#;
         let req_obj = TryInto::<Request>::try_into(req);
+        #[cfg(feature = "case-insensitive")]
+        assert_eq!(req_obj.unwrap().id, Id::Number(1));
+        #[cfg(not(feature = "case-insensitive"))]
         assert!(req_obj.is_err());
+    }
+
+    #[test]
+    fn null_params() {
+        let obj = r#"{"jsonrpc":"2.0","method":"a","params":null,"id":1}"#;
+        assert_eq!(TryInto::<Request>::try_into(obj).unwrap().params, None);
+        let strict = ParseOptions {
+            strict_params: true,
+            ..ParseOptions::default()
+        };
+        assert!(Request::parse_with(obj, &strict).is_err());
 
-        let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id":[]}"#; // id is array
+        let obj = r#"{"jsonrpc":"2.0","method":"a","id":1}"#;
+        assert_eq!(TryInto::<Request>::try_into(obj).unwrap().params, None);
+        assert_eq!(
+            TryInto::<String>::try_into(Request::builder().id(1).method("a").build()).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"a","id":1}"#
+        );
+    }
+
+    #[test]
+    fn scalar_params() {
+        let obj = r#"{"jsonrpc":"2.0","method":"a","params":5,"id":1}"#;
+        assert!(TryInto::<Request>::try_into(obj).is_err());
+        let lenient = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            Request::parse_with(obj, &lenient).unwrap().params,
+            Some(Params::array(vec![5.into()]))
+        );
+    }
+
+    #[test]
+    fn negative_serde_tests() {
+        let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23]}"#; // no id
         let req_obj = TryInto::<Request>::try_into(req);
         assert!(req_obj.is_err());
 
+        #[cfg(not(feature = "raw-id"))]
+        {
+            let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id":{}}"#; // id is obj
+            let req_obj = TryInto::<Request>::try_into(req);
+            assert!(req_obj.is_err());
+
+            let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": [42, 23], "id":[]}"#; // id is array
+            let req_obj = TryInto::<Request>::try_into(req);
+            assert!(req_obj.is_err());
+        }
+
         let req = r#"{"jsonrpc": "2.0", "method": "subtract", "params": 1, "id":2}"#; // params is number
         let req_obj = TryInto::<Request>::try_into(req);
         assert!(req_obj.is_err());
@@ -125,6 +456,172 @@ mod tests {
         assert!(req_obj.is_err());
     }
 
+    #[cfg(feature = "raw-id")]
+    #[test]
+    fn raw_id_echo() {
+        let req = r#"{"jsonrpc":"2.0","method":"a","id":{"session":[1,"x"]}}"#;
+        let req = TryInto::<Request>::try_into(req).unwrap();
+        assert_eq!(req.id, Id::Raw(serde_json::json!({"session": [1, "x"]})));
+
+        let rsp = Response::builder().id(&req).success().build();
+        assert_eq!(
+            TryInto::<String>::try_into(rsp).unwrap(),
+            r#"{"jsonrpc":"2.0","id":{"session":[1,"x"]},"result":null}"#
+        );
+    }
+
+    #[test]
+    fn id_accessors() {
+        let req = Request::builder().id(10).method("test-method").build();
+        assert_eq!(req.id_ref(), &Id::Number(10));
+        assert_eq!(req.id_as_i64(), Some(10));
+        assert_eq!(req.id_as_str(), None);
+
+        let req = Request::builder().id("abc").method("test-method").build();
+        assert_eq!(req.id_ref(), &Id::String("abc".to_string()));
+        assert_eq!(req.id_as_i64(), None);
+        assert_eq!(req.id_as_str(), Some("abc"));
+
+        let req = Request::builder().id(()).method("test-method").build();
+        assert_eq!(req.id_as_i64(), None);
+        assert_eq!(req.id_as_str(), None);
+    }
+
+    #[test]
+    fn builder_method_nonempty() {
+        let req = Request::builder().id(0).method_nonempty("");
+        assert!(matches!(req, Err(Error::InvalidType(_))));
+
+        let req = Request::builder().id(0).method_nonempty("test-method");
+        assert_eq!(req.unwrap().build().method, "test-method");
+    }
+
+    #[test]
+    fn builder_method_owned() {
+        let method = String::from("test-method");
+        let req = Request::builder().id(0).method(method).build();
+        assert_eq!(req.method, "test-method");
+    }
+
+    #[test]
+    fn builder_params_value() {
+        let params = TryInto::<Params>::try_into(r#"{"a":1}"#).unwrap();
+        let req = Request::builder()
+            .id(10)
+            .method("test-method")
+            .params_value(params.clone())
+            .build();
+        assert_eq!(req.params, Some(params));
+    }
+
+    #[test]
+    fn debug_redacted() {
+        let req = Request::builder()
+            .id(1)
+            .method("login")
+            .params_str(r#"{"password":"hunter2"}"#)
+            .unwrap()
+            .build();
+        let debug = format!("{:?}", req.debug_redacted());
+        assert_eq!(
+            debug,
+            r#"Request { jsonrpc: "2.0", method: "login", params: Some(<redacted>), id: Number(1) }"#
+        );
+        assert!(!debug.contains("hunter2"));
+
+        let req = Request::builder().id(1).method("logout").build();
+        let debug = format!("{:?}", req.debug_redacted());
+        assert!(debug.contains("params: None"));
+    }
+
+    #[test]
+    fn error_responses() {
+        use crate::response::Status;
+
+        let req = Request::builder().id("abc").method("test-method").build();
+        let rsp = req.method_not_found_response();
+        assert_eq!(rsp.id, req.id);
+        assert!(matches!(
+            rsp.status,
+            Status::Error {
+                code: -32601,
+                data: None,
+                ..
+            }
+        ));
+
+        let rsp = req.invalid_params_response(None);
+        assert_eq!(rsp.id, req.id);
+        assert!(matches!(
+            rsp.status,
+            Status::Error {
+                code: -32602,
+                data: None,
+                ..
+            }
+        ));
+
+        let rsp = req.invalid_params_response(Some(serde_json::json!("expected array")));
+        assert_eq!(
+            rsp.status,
+            Status::Error {
+                code: -32602,
+                message: "Invalid params".into(),
+                data: Some(serde_json::json!("expected array"))
+            }
+        );
+    }
+
+    #[test]
+    fn take_params() {
+        let mut req = Request::builder()
+            .id(10)
+            .method("test-method")
+            .params_str("[1,2]")
+            .unwrap()
+            .build();
+        let params = req.take_params();
+        assert_eq!(*params.unwrap(), serde_json::json!([1, 2]));
+        assert!(req.params.is_none());
+        assert!(req.take_params().is_none());
+    }
+
+    #[test]
+    fn params_mut() {
+        let mut req = Request::builder().id(10).method("test-method").build();
+        assert!(req.params_mut().is_none());
+
+        let mut req = Request::builder()
+            .id(10)
+            .method("test-method")
+            .params_str("[1,2]")
+            .unwrap()
+            .build();
+        let params = req.params_mut().unwrap();
+        params[0] = serde_json::json!(5);
+        assert!(params.set(serde_json::json!("scalar")).is_err());
+        assert_eq!(*req.params.unwrap(), serde_json::json!([5, 2]));
+    }
+
+    #[test]
+    fn write_to() {
+        let obj = Request::builder()
+            .id(10)
+            .method("test-method")
+            .params_str("[1,2]")
+            .unwrap()
+            .build();
+        let mut buf = Vec::new();
+        obj.write_to(&mut buf).unwrap();
+        #[cfg(feature = "bytes")]
+        {
+            let mut bytes = bytes::BytesMut::new();
+            obj.encode_into(&mut bytes).unwrap();
+            assert_eq!(bytes, buf);
+        }
+        assert_eq!(buf, TryInto::<String>::try_into(obj).unwrap().into_bytes());
+    }
+
     #[test]
     fn builder() {
         let params = vec![10, 0];
@@ -138,4 +635,178 @@ mod tests {
         let new_req = TryInto::<Request>::try_into(req_str.as_str());
         assert!(new_req.is_ok());
     }
+
+    #[test]
+    fn try_from_limited() {
+        let limits = Limits::default();
+        let req = r#"{"jsonrpc":"2.0","method":"a","params":[[1]],"id":1}"#;
+        assert!(Request::try_from_limited(req, &limits).is_ok());
+
+        let params = format!("{}{}", "[".repeat(100), "]".repeat(100));
+        let req = format!(r#"{{"jsonrpc":"2.0","method":"a","params":{params},"id":1}}"#);
+        assert!(matches!(
+            Request::try_from_limited(&req, &limits),
+            Err(Error::LimitExceeded(_))
+        ));
+        assert!(TryInto::<Request>::try_into(req.as_str()).is_ok());
+
+        let req = r#"{"jsonrpc":"2.0","method":"a","id":1"#;
+        assert!(matches!(
+            Request::try_from_limited(req, &limits),
+            Err(Error::Serde(_))
+        ));
+    }
+
+    #[test]
+    fn canonical_string() {
+        let a = TryInto::<Request>::try_into(
+            r#"{"jsonrpc": "2.0", "method": "sign", "params": {"b": [1.50, {"y": 1, "x": "\u0041"}], "a": null}, "id": 7}"#,
+        )
+        .unwrap();
+        let b = TryInto::<Request>::try_into(
+            r#"{"id":7,"params":{"a":null,"b":[1.5,{"x":"A","y":1}]},"method":"sign","jsonrpc":"2.0"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            a.to_canonical_string().unwrap(),
+            r#"{"id":7,"jsonrpc":"2.0","method":"sign","params":{"a":null,"b":[1.5,{"x":"A","y":1}]}}"#
+        );
+        assert_eq!(
+            a.to_canonical_string().unwrap(),
+            b.to_canonical_string().unwrap()
+        );
+
+        let req = Request::builder().id("x").method("a").build();
+        assert_eq!(
+            req.to_canonical_string().unwrap(),
+            r#"{"id":"x","jsonrpc":"2.0","method":"a"}"#
+        );
+    }
+
+    #[test]
+    fn serialized_len() {
+        let req = Request::builder()
+            .id("é")
+            .method("subtract")
+            .params_str(r#"{"a":[1,2.5,"ü"]}"#)
+            .unwrap()
+            .build();
+        let req_str = TryInto::<String>::try_into(req.clone()).unwrap();
+        assert_eq!(req.serialized_len().unwrap(), req_str.len());
+    }
+
+    #[test]
+    fn parts() {
+        let req = Request::builder()
+            .id(7)
+            .method("subtract")
+            .params_str("[42,23]")
+            .unwrap()
+            .build();
+        let (method, params, id) = req.clone().into_parts();
+        assert_eq!(method, "subtract");
+        assert_eq!(id, Id::Number(7));
+        assert_eq!(Request::from_parts(method, params.clone(), id), req);
+
+        let req = Request::from_parts("v2.subtract", params, "abc");
+        assert_eq!(
+            TryInto::<String>::try_into(req).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"v2.subtract","params":[42,23],"id":"abc"}"#
+        );
+    }
+
+    #[test]
+    fn with_context() {
+        let req = Request::builder()
+            .id(1)
+            .method("subtract")
+            .params_str("[42,23]")
+            .unwrap()
+            .build();
+        let with_ctx = WithContext::new(req.clone(), "127.0.0.1:4000".to_string());
+        assert_eq!(with_ctx.method, "subtract");
+        assert_eq!(with_ctx.context, "127.0.0.1:4000");
+        assert_eq!(
+            serde_json::to_string(&with_ctx).unwrap(),
+            TryInto::<String>::try_into(req).unwrap()
+        );
+    }
+
+    #[cfg(feature = "meta")]
+    #[test]
+    fn meta() {
+        let mut req = Request::builder().id(1).method("get").build();
+        assert_eq!(
+            TryInto::<String>::try_into(req.clone()).unwrap(),
+            r#"{"jsonrpc":"2.0","method":"get","id":1}"#
+        );
+
+        req.meta = Some(serde_json::json!({"trace": "abc"}));
+        let req_str = TryInto::<String>::try_into(req.clone()).unwrap();
+        assert_eq!(
+            req_str,
+            r#"{"jsonrpc":"2.0","method":"get","id":1,"meta":{"trace":"abc"}}"#
+        );
+        assert_eq!(TryInto::<Request>::try_into(req_str.as_str()).unwrap(), req);
+    }
+
+    #[test]
+    fn dedup() {
+        let a = TryInto::<Request>::try_into(
+            r#"{"jsonrpc":"2.0","method":"get","params":{"a":1,"b":2},"id":1}"#,
+        )
+        .unwrap();
+        let b = TryInto::<Request>::try_into(
+            r#"{"id":1,"params":{"b":2,"a":1},"method":"get","jsonrpc":"2.0"}"#,
+        )
+        .unwrap();
+        let c = Request::builder().id(2).method("get").build();
+        let set: std::collections::HashSet<Request> = [a.clone(), b, c.clone(), a].into();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&c));
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn json_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(Request)).unwrap();
+        assert_eq!(schema["properties"]["jsonrpc"]["const"], "2.0");
+        assert_eq!(
+            schema["$defs"]["Id"]["type"],
+            serde_json::json!(["string", "number", "null"])
+        );
+        assert_eq!(
+            schema["$defs"]["Params"]["type"],
+            serde_json::json!(["object", "array"])
+        );
+        let required = schema["required"].as_array().unwrap();
+        assert!(required.contains(&"method".into()));
+        assert!(required.contains(&"id".into()));
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_round_trip() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect();
+        let mut u = Unstructured::new(&bytes);
+        while !u.is_empty() {
+            let obj = Request::arbitrary(&mut u).unwrap();
+            let obj_str = TryInto::<String>::try_into(obj.clone()).unwrap();
+            assert_eq!(TryInto::<Request>::try_into(obj_str.as_str()).unwrap(), obj);
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn proptest_round_trip(req in arb_request()) {
+            let req_str = TryInto::<String>::try_into(req.clone()).unwrap();
+            let new_req = TryInto::<Request>::try_into(req_str.as_str()).unwrap();
+            proptest::prop_assert_eq!(new_req, req);
+        }
+    }
 }
@@ -1,6 +1,6 @@
 //! This module implements a Builder class for the Request object.
 
-use crate::{error::Error, id::Id as JId, params::Params, request::Request};
+use crate::{error::Error, id::Id as JId, params::Params, request::Request, version::Version};
 
 // =======================
 // Type State Structs
@@ -12,6 +12,7 @@ pub struct Id(JId);
 // =======================
 
 /// The Builder class for a Request object.
+#[must_use = "builders do nothing unless `build()` is called"]
 pub struct Builder<M, I> {
     method: M,
     params: Option<Params>,
@@ -35,6 +36,7 @@ impl Builder<MethodNone, IdNone> {
 }
 
 impl<M, I> Builder<M, I> {
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params(self, p: serde_json::Value) -> Result<Builder<M, I>, Error> {
         Ok(Builder {
             method: self.method,
@@ -43,6 +45,7 @@ impl<M, I> Builder<M, I> {
         })
     }
 
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params_serialize<T: serde::Serialize>(self, p: T) -> Result<Builder<M, I>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         let params = Params::try_from(value)?;
@@ -53,6 +56,16 @@ impl<M, I> Builder<M, I> {
         })
     }
 
+    /// Sets already validated params, so no conversion can fail.
+    pub fn params_value(self, p: Params) -> Builder<M, I> {
+        Builder {
+            method: self.method,
+            params: Some(p),
+            id: self.id,
+        }
+    }
+
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn params_str(self, p: &str) -> Result<Builder<M, I>, Error> {
         let params = Params::try_from(p)?;
         Ok(Builder {
@@ -64,21 +77,51 @@ impl<M, I> Builder<M, I> {
 }
 
 impl<I> Builder<MethodNone, I> {
-    pub fn method(self, m: &str) -> Builder<Method, I> {
+    pub fn method<T: Into<String>>(self, m: T) -> Builder<Method, I> {
+        let m = m.into();
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if m.is_empty() {
+            crate::diagnostics::discouraged(format_args!(
+                "building a request with an empty method name"
+            ));
+        }
         Builder {
-            method: Method(m.to_string()),
+            method: Method(m),
             params: self.params,
             id: self.id,
         }
     }
+
+    /// Same as [`Builder::method`], but rejects an empty method name.
+    ///
+    /// The spec doesn't forbid empty method names, but in practice one is always a client bug.
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
+    pub fn method_nonempty<T: Into<String>>(self, m: T) -> Result<Builder<Method, I>, Error> {
+        let m = m.into();
+        if m.is_empty() {
+            return Err(Error::InvalidType("method must not be empty".to_string()));
+        }
+        Ok(self.method(m))
+    }
 }
 
 impl<M> Builder<M, IdNone> {
     pub fn id<T: Into<JId>>(self, i: T) -> Builder<M, Id> {
+        let i = i.into();
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        match i {
+            JId::Null => crate::diagnostics::discouraged(format_args!(
+                "building a request with a null id, which is reserved for unknown request ids"
+            )),
+            JId::Fractional(v) => crate::diagnostics::discouraged(format_args!(
+                "building a request with fractional id {v}, which may not be represented exactly"
+            )),
+            _ => {}
+        }
         Builder {
             method: self.method,
             params: self.params,
-            id: Id(i.into()),
+            id: Id(i),
         }
     }
 }
@@ -86,7 +129,9 @@ impl<M> Builder<M, IdNone> {
 impl Builder<Method, Id> {
     pub fn build(self) -> Request {
         Request {
-            jsonrpc: "2.0".to_string(),
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
             method: self.method.0,
             params: self.params,
             id: self.id.0,
@@ -1,46 +1,789 @@
 //! This module implements the response JSON-RPC object.
 
-use crate::{error::Error, id::Id};
+use std::borrow::Cow;
+
+use thiserror::Error as ThisError;
+
+use crate::{error::Error, id::Id, options::ParseOptions, version::Version};
 
 pub mod builder;
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The JSON-RPC Response Object
 pub struct Response {
-    #[serde(deserialize_with = "crate::version::version_deserialize")]
-    jsonrpc: String,
+    jsonrpc: Version,
     pub id: Id,
-    #[serde(flatten)]
+    #[cfg_attr(feature = "schemars", schemars(flatten))]
     pub status: Status,
+    /// A "meta" object used by some frameworks. This is an extension beyond the spec, only
+    /// serialized when present.
+    #[cfg(feature = "meta")]
+    pub meta: Option<serde_json::Value>,
+    /// The raw text of a result set with `SuccessBuilder::result_raw`.
+    #[cfg(feature = "raw")]
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    raw_result: Option<RawResult>,
 }
 
-#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 /// The Response Status can be either success or failure.
+///
+/// It deserializes from an object holding exactly one of "result" or "error", and nothing else.
 pub enum Status {
     #[serde(rename = "result")]
     Success(serde_json::Value),
     #[serde(rename = "error")]
     Error {
         code: i32,
-        message: String,
+        message: Cow<'static, str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         data: Option<serde_json::Value>,
     },
 }
 
+#[cfg(feature = "raw")]
+#[derive(Debug, Clone)]
+/// A success result kept as raw JSON text, along with the value it parses to.
+///
+/// The status holds the parsed value, and the raw text is serialized verbatim instead as long
+/// as the status still holds that value, i.e. until the result is replaced.
+pub(crate) struct RawResult {
+    raw: Box<serde_json::value::RawValue>,
+    pub(crate) value: serde_json::Value,
+}
+
+#[cfg(feature = "raw")]
+impl RawResult {
+    pub(crate) fn new(raw: Box<serde_json::value::RawValue>) -> RawResult {
+        // A RawValue always holds valid JSON, so this only loses precision and formatting.
+        let value = serde_json::from_str(raw.get()).unwrap_or_default();
+        RawResult { raw, value }
+    }
+
+    fn get(&self, status: &Status) -> Option<&serde_json::value::RawValue> {
+        matches!(status, Status::Success(v) if *v == self.value).then_some(&*self.raw)
+    }
+}
+
+/// Responses compare by their content, however a result was set.
+impl PartialEq for Response {
+    fn eq(&self, other: &Self) -> bool {
+        #[cfg(feature = "meta")]
+        if self.meta != other.meta {
+            return false;
+        }
+        self.jsonrpc == other.jsonrpc && self.id == other.id && self.status == other.status
+    }
+}
+
+impl serde::Serialize for Response {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        #[derive(serde::Serialize)]
+        #[serde(untagged)]
+        enum StatusRef<'a> {
+            #[cfg(feature = "raw")]
+            Raw {
+                result: &'a serde_json::value::RawValue,
+            },
+            Status(&'a Status),
+        }
+
+        #[derive(serde::Serialize)]
+        struct ResponseRef<'a> {
+            jsonrpc: &'a Version,
+            id: &'a Id,
+            #[serde(flatten)]
+            status: StatusRef<'a>,
+            #[cfg(feature = "meta")]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            meta: Option<&'a serde_json::Value>,
+        }
+
+        #[cfg(feature = "raw")]
+        let raw = self.raw_result.as_ref().and_then(|r| r.get(&self.status));
+        #[cfg(feature = "raw")]
+        let status = match raw {
+            Some(result) => StatusRef::Raw { result },
+            None => StatusRef::Status(&self.status),
+        };
+        #[cfg(not(feature = "raw"))]
+        let status = StatusRef::Status(&self.status);
+        ResponseRef {
+            jsonrpc: &self.jsonrpc,
+            id: &self.id,
+            status,
+            #[cfg(feature = "meta")]
+            meta: self.meta.as_ref(),
+        }
+        .serialize(serializer)
+    }
+}
+
+// Responses are deserialized by reading their fields directly. A derived, flattened Status
+// would buffer the input, couldn't tell a present "result": null from a missing "result", and
+// would pick one arbitrarily when both "result" and "error" are present.
+
+/// The error object is fully specified, so anything else in it is malformed.
+#[derive(serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ErrorObject {
+    #[cfg_attr(feature = "case-insensitive", serde(alias = "Code", alias = "CODE"))]
+    #[serde(deserialize_with = "deserialize_code")]
+    code: i32,
+    #[cfg_attr(
+        feature = "case-insensitive",
+        serde(alias = "Message", alias = "MESSAGE")
+    )]
+    message: String,
+    #[cfg_attr(feature = "case-insensitive", serde(alias = "Data", alias = "DATA"))]
+    data: Option<serde_json::Value>,
+}
+
+// Some non-conformant servers send the code as a string, e.g. "-32601", which is accepted
+// when parsing with `ParseOptions::lenient`.
+fn deserialize_code<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    if !crate::options::current().lenient {
+        return <i32 as serde::Deserialize>::deserialize(deserializer);
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum Code {
+        Number(i32),
+        String(String),
+    }
+
+    match <Code as serde::Deserialize>::deserialize(deserializer)? {
+        Code::Number(v) => Ok(v),
+        Code::String(v) => v
+            .trim()
+            .parse()
+            .map_err(|_| serde::de::Error::custom(format!("error code is not an integer: {v}"))),
+    }
+}
+
+enum Field {
+    Jsonrpc,
+    Id,
+    Result,
+    Error,
+    #[cfg(feature = "meta")]
+    Meta,
+    Other,
+}
+
+impl<'de> serde::Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FieldVisitor;
+        impl serde::de::Visitor<'_> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a response field name")
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(match v {
+                    "jsonrpc" => Field::Jsonrpc,
+                    "id" => Field::Id,
+                    "result" => Field::Result,
+                    "error" => Field::Error,
+                    #[cfg(feature = "meta")]
+                    "meta" => Field::Meta,
+                    #[cfg(feature = "case-insensitive")]
+                    "Jsonrpc" | "JSONRPC" => Field::Jsonrpc,
+                    #[cfg(feature = "case-insensitive")]
+                    "Id" | "ID" => Field::Id,
+                    #[cfg(feature = "case-insensitive")]
+                    "Result" | "RESULT" => Field::Result,
+                    #[cfg(feature = "case-insensitive")]
+                    "Error" | "ERROR" => Field::Error,
+                    _ => Field::Other,
+                })
+            }
+        }
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+/// The fields of a Response object, as read from the input.
+#[derive(Default)]
+struct Fields {
+    jsonrpc: Option<Version>,
+    id: Option<Id>,
+    result: Option<serde_json::Value>,
+    error: Option<ErrorObject>,
+    #[cfg(feature = "meta")]
+    meta: Option<Option<serde_json::Value>>,
+}
+
+impl Fields {
+    /// Reads the fields of `map`. Without `envelope`, only "result" and "error" are allowed.
+    fn read<'de, A>(mut map: A, envelope: bool) -> Result<Fields, A::Error>
+    where
+        A: serde::de::MapAccess<'de>,
+    {
+        fn set<T, E: serde::de::Error>(
+            slot: &mut Option<T>,
+            value: T,
+            name: &'static str,
+        ) -> Result<(), E> {
+            if slot.is_some() {
+                return Err(E::duplicate_field(name));
+            }
+            *slot = Some(value);
+            Ok(())
+        }
+
+        let mut fields = Fields::default();
+        while let Some(key) = map.next_key::<Field>()? {
+            match key {
+                Field::Result => set(&mut fields.result, map.next_value()?, "result")?,
+                Field::Error => set(&mut fields.error, map.next_value()?, "error")?,
+                Field::Jsonrpc if envelope => {
+                    set(&mut fields.jsonrpc, map.next_value()?, "jsonrpc")?
+                }
+                Field::Id if envelope => set(&mut fields.id, map.next_value()?, "id")?,
+                #[cfg(feature = "meta")]
+                Field::Meta if envelope => set(&mut fields.meta, map.next_value()?, "meta")?,
+                Field::Other if envelope => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                }
+                _ => {
+                    return Err(serde::de::Error::custom(
+                        r#"unknown field, expected "result" or "error""#,
+                    ));
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    fn status<E: serde::de::Error>(
+        result: Option<serde_json::Value>,
+        error: Option<ErrorObject>,
+    ) -> Result<Status, E> {
+        match (result, error) {
+            (Some(result), None) => Ok(Status::Success(result)),
+            (None, Some(error)) => Ok(Status::Error {
+                code: error.code,
+                message: error.message.into(),
+                data: error.data,
+            }),
+            (Some(_), Some(_)) => Err(E::custom(
+                r#"response MUST NOT contain both "result" and "error""#,
+            )),
+            (None, None) => Err(E::custom(
+                r#"response MUST contain either "result" or "error""#,
+            )),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Response {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct ResponseVisitor;
+        impl<'de> serde::de::Visitor<'de> for ResponseVisitor {
+            type Value = Response;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a JSON-RPC response object")
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let fields = Fields::read(map, true)?;
+                // Some minimal servers omit "jsonrpc" altogether.
+                let jsonrpc = match fields.jsonrpc {
+                    Some(jsonrpc) => jsonrpc,
+                    None if crate::options::current().lenient => Version::default(),
+                    None => return Err(serde::de::Error::missing_field("jsonrpc")),
+                };
+                let id = fields
+                    .id
+                    .ok_or_else(|| serde::de::Error::missing_field("id"))?;
+                Ok(Response {
+                    jsonrpc,
+                    id,
+                    status: Fields::status(fields.result, fields.error)?,
+                    #[cfg(feature = "meta")]
+                    meta: fields.meta.flatten(),
+                    #[cfg(feature = "raw")]
+                    raw_result: None,
+                })
+            }
+        }
+        deserializer.deserialize_map(ResponseVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Status {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct StatusVisitor;
+        impl<'de> serde::de::Visitor<'de> for StatusVisitor {
+            type Value = Status;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str(r#"an object with either "result" or "error""#)
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                let fields = Fields::read(map, false)?;
+                Fields::status(fields.result, fields.error)
+            }
+        }
+        deserializer.deserialize_map(StatusVisitor)
+    }
+}
+
 impl Response {
     pub fn builder() -> builder::Builder<builder::IdNone> {
         builder::Builder::new()
     }
+
+    /// Parses a response with `options`, e.g. to accept the deviations of a non-conformant peer.
+    pub fn parse_with(value: &str, options: &ParseOptions) -> Result<Response, Error> {
+        crate::options::with(options, || Response::try_from(value))
+    }
+
+    pub fn new<I: Into<Id>>(id: I, status: Status) -> Response {
+        Response {
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
+            #[cfg(feature = "raw")]
+            raw_result: None,
+            id: id.into(),
+            status,
+        }
+    }
+
+    /// Builds an error response for `id` from a [`ResponseError`], e.g. when a proxy forwards an
+    /// upstream error under a new id.
+    #[must_use]
+    pub fn from_response_error<I: Into<Id>>(id: I, err: &ResponseError) -> Response {
+        Response::new(
+            id,
+            Status::Error {
+                code: err.code,
+                message: err.message.clone().into(),
+                data: err.data.clone(),
+            },
+        )
+    }
+
+    /// Builds an error response, checking the code against the range the spec reserves
+    /// (-32768 to -32000): reserved codes that are neither predefined nor server errors are
+    /// rejected, and a predefined code with a message other than its predefined one is reported
+    /// as discouraged, since it's usually an application error reusing a reserved code.
+    ///
+    /// The builder emits any code unchecked.
+    pub fn new_error_from_code<I: Into<Id>>(
+        id: I,
+        code: i32,
+        message: &str,
+        data: Option<serde_json::Value>,
+    ) -> Result<Response, Error> {
+        let predefined = match ErrorKind::from_code(code) {
+            ErrorKind::Parse => Some("Parse error"),
+            ErrorKind::InvalidRequest => Some("Invalid Request"),
+            ErrorKind::MethodNotFound => Some("Method not found"),
+            ErrorKind::InvalidParams => Some("Invalid params"),
+            ErrorKind::Internal => Some("Internal error"),
+            ErrorKind::Server => None,
+            ErrorKind::Application if (-32768..=-32000).contains(&code) => {
+                return Err(Error::InvalidType(format!(
+                    "error code {code} is reserved by the spec"
+                )));
+            }
+            ErrorKind::Application => None,
+        };
+        #[cfg(any(feature = "log", feature = "tracing"))]
+        if let Some(predefined) = predefined.filter(|p| *p != message) {
+            crate::diagnostics::discouraged(format_args!(
+                "error code {code} is predefined as {predefined:?}, but has message {message:?}"
+            ));
+        }
+        #[cfg(not(any(feature = "log", feature = "tracing")))]
+        let _ = predefined;
+        Ok(Response::new(
+            id,
+            Status::Error {
+                code,
+                message: message.to_string().into(),
+                data,
+            },
+        ))
+    }
+
+    /// The "jsonrpc" version of the response, "2.0" unless parsed with
+    /// `ParseOptions::forward_compatible`.
+    pub fn version(&self) -> &str {
+        self.jsonrpc.as_str()
+    }
+
+    /// Serializes the response straight into `w`, without an intermediate `String`.
+    pub fn write_to<W: std::io::Write>(&self, w: W) -> Result<(), Error> {
+        serde_json::to_writer(w, self).map_err(|e| e.into())
+    }
+
+    /// Serializes the response straight into `buf`, e.g. in a `tokio_util::codec::Encoder`.
+    #[cfg(feature = "bytes")]
+    pub fn encode_into(&self, buf: &mut bytes::BytesMut) -> Result<(), Error> {
+        self.write_to(bytes::BufMut::writer(buf))
+    }
+
+    /// Serializes the response with an explicit "status" tag, e.g. `{"status":"success",...}`.
+    ///
+    /// This is meant for logs and internal tooling, it's NOT the spec's wire format.
+    #[cfg(feature = "tagged-debug")]
+    pub fn to_tagged_string(&self) -> Result<String, Error> {
+        #[derive(serde::Serialize)]
+        #[serde(tag = "status", rename_all = "lowercase")]
+        enum Tagged<'a> {
+            Success(&'a Status),
+            Error(&'a Status),
+        }
+
+        #[derive(serde::Serialize)]
+        struct TaggedResponse<'a> {
+            jsonrpc: &'a Version,
+            id: &'a Id,
+            #[serde(flatten)]
+            status: Tagged<'a>,
+        }
+
+        let status = match &self.status {
+            Status::Error { .. } => Tagged::Error(&self.status),
+            _ => Tagged::Success(&self.status),
+        };
+        let tagged = TaggedResponse {
+            jsonrpc: &self.jsonrpc,
+            id: &self.id,
+            status,
+        };
+        serde_json::to_string(&tagged).map_err(|e| e.into())
+    }
+
+    /// Decodes a binary result set with `SuccessBuilder::result_bytes`, from a standard base64 string.
+    #[cfg(feature = "base64")]
+    pub fn result_bytes(&self) -> Result<Vec<u8>, Error> {
+        use base64::Engine;
+
+        let Status::Success(serde_json::Value::String(encoded)) = &self.status else {
+            return Err(Error::InvalidType(
+                "result is not a base64 string".to_string(),
+            ));
+        };
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(|e| Error::InvalidType(format!("result is not valid base64: {e}")))
+    }
+
+    /// Decodes a 128-bit integer result set with `SuccessBuilder::result_u128`, from a decimal string.
+    pub fn result_as_u128(&self) -> Result<u128, Error> {
+        self.result_decimal_str()?
+            .parse()
+            .map_err(|e| Error::InvalidType(format!("result is not a u128: {e}")))
+    }
+
+    /// Decodes a 128-bit integer result set with `SuccessBuilder::result_i128`, from a decimal string.
+    pub fn result_as_i128(&self) -> Result<i128, Error> {
+        self.result_decimal_str()?
+            .parse()
+            .map_err(|e| Error::InvalidType(format!("result is not an i128: {e}")))
+    }
+
+    fn result_decimal_str(&self) -> Result<&str, Error> {
+        match &self.status {
+            Status::Success(serde_json::Value::String(s)) => Ok(s),
+            _ => Err(Error::InvalidType(
+                "result is not a decimal string".to_string(),
+            )),
+        }
+    }
+
+    /// Compares two responses, ignoring the error `data`, which often holds volatile content.
+    ///
+    /// Responses match if they have the same id and either both succeeded with equal results,
+    /// or both failed with the same code and message.
+    pub fn matches_ignoring_data(&self, other: &Response) -> bool {
+        if self.id != other.id {
+            return false;
+        }
+        match (&self.status, &other.status) {
+            (Status::Success(a), Status::Success(b)) => a == b,
+            (
+                Status::Error {
+                    code: a_code,
+                    message: a_message,
+                    ..
+                },
+                Status::Error {
+                    code: b_code,
+                    message: b_message,
+                    ..
+                },
+            ) => a_code == b_code && a_message == b_message,
+            _ => false,
+        }
+    }
+
+    /// Checks for responses that parse fine but are almost certainly a server bug.
+    ///
+    /// A null id is reserved for errors where the request id couldn't be determined, so a
+    /// success response with a null id fails with [`Error::InvalidType`].
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.id == Id::Null && !matches!(self.status, Status::Error { .. }) {
+            return Err(Error::InvalidType(
+                "success response must not have a null id".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that this response answers the request with the `expected` id.
+    pub fn ensure_id_matches(&self, expected: &Id) -> Result<(), Error> {
+        if &self.id != expected {
+            return Err(Error::IdMismatch {
+                expected: expected.clone(),
+                got: self.id.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that this response is a sane answer to `req`, for strict clients: the version
+    /// must match ([`Error::InvalidVersion`]), the id must correlate ([`Error::IdMismatch`]),
+    /// and the response must pass [`Response::validate`].
+    ///
+    /// An error response with a null id is accepted, since the server may have been unable to
+    /// read the request id.
+    pub fn validate_against(&self, req: &crate::request::Request) -> Result<(), Error> {
+        if self.version() != req.version() {
+            return Err(Error::InvalidVersion(format!(
+                "response version {} doesn't match request version {}",
+                self.version(),
+                req.version()
+            )));
+        }
+        if !(self.id == Id::Null && self.is_error()) {
+            self.ensure_id_matches(&req.id)?;
+        }
+        self.validate()
+    }
+
+    /// Transforms the success result with `f`, leaving error responses untouched.
+    #[must_use]
+    pub fn map_result<F: FnOnce(serde_json::Value) -> serde_json::Value>(self, f: F) -> Response {
+        let status = match self.status {
+            Status::Success(v) => Status::Success(f(v)),
+            status => status,
+        };
+        Response { status, ..self }
+    }
+
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    pub fn is_success(&self) -> bool {
+        !self.is_error()
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.status, Status::Error { .. })
+    }
+
+    /// Returns "success" or "error", e.g. as a field in structured logs.
+    pub fn status_discriminant(&self) -> &'static str {
+        if self.is_error() { "error" } else { "success" }
+    }
+
+    /// Classifies the error of this response by its code, or returns None for a success.
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        match &self.status {
+            Status::Error { code, .. } => Some(ErrorKind::from_code(*code)),
+            _ => None,
+        }
+    }
+
+    /// Splits off error responses as a [`ResponseError`], so they can be propagated with `?`.
+    pub fn into_error(self) -> Result<Response, ResponseError> {
+        match self.status {
+            Status::Success(_) => Ok(self),
+            Status::Error {
+                code,
+                message,
+                data,
+            } => Err(ResponseError {
+                code,
+                message: message.into_owned(),
+                data,
+            }),
+        }
+    }
+}
+
+/// Builds the error response for input that failed to parse as a Request, with a null id.
+///
+/// Invalid or empty JSON gets a -32700 "Parse error", while valid JSON that isn't a valid Request (e.g.
+/// a wrong "jsonrpc" version) gets a -32600 "Invalid Request", as the spec requires.
+pub fn error_response_for_parse_failure(err: &Error) -> Response {
+    let builder = Response::builder().id(()).error();
+    match err {
+        Error::Serde(e) if e.is_syntax() || e.is_eof() => builder.parse_error().build(),
+        Error::EmptyInput => builder.parse_error().build(),
+        _ => builder.invalid_request().build(),
+    }
+}
+
+/// Builds an error response carrying the request id if it could be parsed, or a null id if not,
+/// as the spec requires, e.g. for a request whose id parsed but whose params are invalid.
+pub fn error_response(parsed_id: Option<Id>, code: i32, message: &str) -> Response {
+    Response::new(parsed_id.unwrap_or(Id::Null), (code, message).into())
+}
+
+impl From<(i32, &str)> for Status {
+    fn from((code, message): (i32, &str)) -> Self {
+        Status::Error {
+            code,
+            message: message.to_string().into(),
+            data: None,
+        }
+    }
+}
+
+impl From<(i32, &str, serde_json::Value)> for Status {
+    fn from((code, message, data): (i32, &str, serde_json::Value)) -> Self {
+        Status::Error {
+            code,
+            message: message.to_string().into(),
+            data: Some(data),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+/// The class of an error response, telling protocol errors apart from application errors.
+///
+/// Kinds are ordered as declared, so the spec's predefined errors sort first, then server
+/// errors, then application errors.
+pub enum ErrorKind {
+    /// -32700, the server received invalid JSON.
+    Parse,
+    /// -32600, the JSON sent is not a valid Request object.
+    InvalidRequest,
+    /// -32601, the method does not exist or is not available.
+    MethodNotFound,
+    /// -32602, invalid method parameters.
+    InvalidParams,
+    /// -32603, internal JSON-RPC error.
+    Internal,
+    /// -32099 to -32000, reserved for implementation-defined server errors.
+    Server,
+    /// Any other code, defined by the application.
+    Application,
+}
+
+impl ErrorKind {
+    pub fn from_code(code: i32) -> ErrorKind {
+        match code {
+            -32700 => ErrorKind::Parse,
+            -32600 => ErrorKind::InvalidRequest,
+            -32601 => ErrorKind::MethodNotFound,
+            -32602 => ErrorKind::InvalidParams,
+            -32603 => ErrorKind::Internal,
+            -32099..=-32000 => ErrorKind::Server,
+            _ => ErrorKind::Application,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, ThisError)]
+#[error("JSON-RPC error {code}: {message}")]
+/// The error object of an error Response, usable as a Rust error.
+pub struct ResponseError {
+    pub code: i32,
+    pub message: String,
+    pub data: Option<serde_json::Value>,
+}
+
+impl TryFrom<Response> for ResponseError {
+    type Error = Error;
+
+    fn try_from(value: Response) -> Result<Self, Self::Error> {
+        match value.into_error() {
+            Ok(_) => Err(Error::InvalidType(
+                "cannot convert success Response to ResponseError".to_string(),
+            )),
+            Err(e) => Ok(e),
+        }
+    }
+}
+
+/// Turns a handler result into the response for `id`, e.g. `Response::try_from((id, output))?`.
+///
+/// The `Ok` value is serialized into the success result, which is the only possible failure.
+impl<T: serde::Serialize> TryFrom<(Id, Result<T, ResponseError>)> for Response {
+    type Error = Error;
+
+    fn try_from((id, result): (Id, Result<T, ResponseError>)) -> Result<Self, Self::Error> {
+        let status = match result {
+            Ok(value) => Status::Success(serde_json::to_value(value)?),
+            Err(e) => Status::Error {
+                code: e.code,
+                message: e.message.into(),
+                data: e.data,
+            },
+        };
+        Ok(Response::new(id, status))
+    }
 }
 
 impl TryFrom<&str> for Response {
     type Error = Error;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value.as_bytes())?;
         serde_json::from_str(value).map_err(|e| e.into())
     }
 }
 
+impl TryFrom<&[u8]> for Response {
+    type Error = Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        crate::error::check_not_empty(value)?;
+        serde_json::from_slice(value).map_err(|e| e.into())
+    }
+}
+
 impl TryFrom<Response> for String {
     type Error = Error;
 
@@ -49,6 +792,91 @@ impl TryFrom<Response> for String {
     }
 }
 
+// Like `From<&Request>`, so a new request or response can reuse a correlated id, e.g.
+// `Response::builder().id(&rsp)`.
+impl From<&Response> for Id {
+    fn from(value: &Response) -> Self {
+        value.id.clone()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Response {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Response {
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
+            #[cfg(feature = "raw")]
+            raw_result: None,
+            id: u.arbitrary()?,
+            status: u.arbitrary()?,
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Status {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? {
+            return Ok(Status::Success(crate::params::arbitrary_value(
+                u,
+                crate::params::ARBITRARY_DEPTH,
+            )?));
+        }
+        // "data": null reads back as an absent "data", so only generate meaningful data.
+        let data = if u.arbitrary::<bool>()? {
+            Some(crate::params::arbitrary_value(
+                u,
+                crate::params::ARBITRARY_DEPTH,
+            )?)
+            .filter(|v| !v.is_null())
+        } else {
+            None
+        };
+        Ok(Status::Error {
+            code: u.arbitrary()?,
+            message: u.arbitrary::<String>()?.into(),
+            data,
+        })
+    }
+}
+
+/// A `proptest` strategy producing valid [`Response`] objects.
+#[cfg(feature = "proptest")]
+pub fn arb_response() -> impl proptest::strategy::Strategy<Value = Response> {
+    use proptest::prelude::*;
+
+    (crate::id::arb_id(), arb_status()).prop_map(|(id, status)| Response {
+        jsonrpc: Version::V2_0,
+        #[cfg(feature = "meta")]
+        meta: None,
+        #[cfg(feature = "raw")]
+        raw_result: None,
+        id,
+        status,
+    })
+}
+
+/// A `proptest` strategy producing either a success or an error [`Status`].
+#[cfg(feature = "proptest")]
+pub fn arb_status() -> impl proptest::strategy::Strategy<Value = Status> {
+    use proptest::prelude::*;
+
+    // "data": null reads back as an absent "data", so only generate meaningful data.
+    let data = crate::params::arb_value().prop_filter("null data", |v| !v.is_null());
+    prop_oneof![
+        crate::params::arb_value().prop_map(Status::Success),
+        (any::<i32>(), any::<String>(), proptest::option::of(data)).prop_map(
+            |(code, message, data)| Status::Error {
+                code,
+                message: message.into(),
+                data,
+            }
+        ),
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +932,155 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn falsy_success_results() {
+        let rsp = r#"{"jsonrpc":"2.0","result":null,"id":1}"#;
+        let rsp_obj = TryInto::<Response>::try_into(rsp);
+        assert!(rsp_obj.is_ok(), "{:?}", rsp_obj.unwrap_err().to_string());
+        let rsp_obj = rsp_obj.unwrap();
+        assert_eq!(rsp_obj.status, Status::Success(serde_json::Value::Null));
+        let rsp_str = TryInto::<String>::try_into(rsp_obj).unwrap();
+        assert_eq!(rsp_str, r#"{"jsonrpc":"2.0","id":1,"result":null}"#);
+
+        let rsp = Response::builder().id(1).success().build();
+        assert_eq!(rsp.status, Status::Success(serde_json::Value::Null));
+        let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+        assert_eq!(
+            TryInto::<Response>::try_into(rsp_str.as_str()).unwrap(),
+            rsp
+        );
+
+        let rsp = r#"{"jsonrpc":"2.0","result":false,"id":1}"#;
+        let rsp_obj = TryInto::<Response>::try_into(rsp);
+        assert!(rsp_obj.is_ok(), "{:?}", rsp_obj.unwrap_err().to_string());
+        let rsp_obj = rsp_obj.unwrap();
+        assert_eq!(
+            rsp_obj.status,
+            Status::Success(serde_json::Value::Bool(false))
+        );
+        let rsp_str = TryInto::<String>::try_into(rsp_obj).unwrap();
+        assert_eq!(rsp_str, r#"{"jsonrpc":"2.0","id":1,"result":false}"#);
+    }
+
+    #[test]
+    fn deserialize_bytes() {
+        let rsp = br#"{"jsonrpc": "2.0", "result": 19, "id": 1}"#;
+        let rsp_obj = TryInto::<Response>::try_into(&rsp[..]);
+        assert!(rsp_obj.is_ok());
+        assert_eq!(rsp_obj.unwrap().id, Id::Number(1));
+
+        let rsp = b"{\"jsonrpc\": \"2.0\", \"result\": \"\xff\", \"id\": 1}"; // invalid utf-8
+        let rsp_obj = TryInto::<Response>::try_into(&rsp[..]);
+        assert!(rsp_obj.is_err());
+    }
+
+    #[test]
+    fn capitalized_field_names() {
+        let rsp = r#"{"Jsonrpc": "2.0", "Error": {"Code": -32601, "Message": "Method not found"}, "Id": 1}"#;
+        let rsp_obj = TryInto::<Response>::try_into(rsp);
+        #[cfg(feature = "case-insensitive")]
+        assert!(matches!(
+            rsp_obj.unwrap().status,
+            Status::Error { code: -32601, .. }
+        ));
+        #[cfg(not(feature = "case-insensitive"))]
+        assert!(rsp_obj.is_err());
+    }
+
+    #[test]
+    fn string_error_code() {
+        let rsp = r#"{"jsonrpc": "2.0", "error": {"code": "-32601", "message": "Method not found"}, "id": 1}"#;
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+        let lenient = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert!(matches!(
+            Response::parse_with(rsp, &lenient).unwrap().status,
+            Status::Error { code: -32601, .. }
+        ));
+
+        let rsp = r#"{"jsonrpc": "2.0", "error": {"code": "abc", "message": "Method not found"}, "id": 1}"#;
+        assert!(Response::parse_with(rsp, &lenient).is_err());
+    }
+
+    #[test]
+    fn deserialize_fields() {
+        let rsp = r#"{"id": 1, "extra": {"x": [1]}, "result": null, "jsonrpc": "2.0"}"#;
+        let rsp_obj = TryInto::<Response>::try_into(rsp).unwrap();
+        assert_eq!(rsp_obj.status, Status::Success(serde_json::Value::Null));
+
+        let rsp = r#"{"jsonrpc": "2.0", "result": 1, "result": 2, "id": 1}"#; // duplicate result
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+        let rsp = r#"{"jsonrpc": "2.0", "result": 1, "id": 1, "id": 2}"#; // duplicate id
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+        let rsp = r#"{"jsonrpc": "2.0", "result": 1, "id": 1, "jsonrpc": "2.0"}"#; // duplicate jsonrpc
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+        let rsp = r#"["2.0", 1, 1]"#; // not an object
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+
+        let status = serde_json::from_str::<Status>(r#"{"result": [1]}"#).unwrap();
+        assert_eq!(status, Status::Success(serde_json::json!([1])));
+        let status = serde_json::from_str::<Status>(r#"{"error": {"code": -1, "message": "x"}}"#);
+        assert_eq!(status.unwrap(), Status::from((-1, "x")));
+        assert!(serde_json::from_str::<Status>(r#"{"result": 1, "id": 1}"#).is_err());
+        assert!(serde_json::from_str::<Status>(r#"{}"#).is_err());
+        assert!(
+            serde_json::from_str::<Status>(
+                r#"{"result": 1, "error": {"code": -1, "message": "x"}}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[cfg(feature = "meta")]
+    #[test]
+    fn meta() {
+        let mut rsp = Response::builder()
+            .id(1)
+            .success()
+            .result(true.into())
+            .build();
+        assert_eq!(
+            TryInto::<String>::try_into(rsp.clone()).unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"result":true}"#
+        );
+
+        rsp.meta = Some(serde_json::json!({"cached": true}));
+        let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"result":true,"meta":{"cached":true}}"#
+        );
+        assert_eq!(
+            TryInto::<Response>::try_into(rsp_str.as_str()).unwrap(),
+            rsp
+        );
+        assert!(
+            TryInto::<Response>::try_into(
+                r#"{"jsonrpc":"2.0","id":1,"result":true,"meta":1,"meta":2}"#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn missing_version() {
+        let rsp = r#"{"result": 19, "id": 1}"#;
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+        let lenient = ParseOptions {
+            lenient: true,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            Response::parse_with(rsp, &lenient).unwrap().version(),
+            "2.0"
+        );
+
+        let rsp = r#"{"jsonrpc": "1.0", "result": 19, "id": 1}"#;
+        assert!(TryInto::<Response>::try_into(rsp).is_err());
+    }
+
     #[test]
     fn negative_serde_tests() {
         let req = r#"{"jsonrpc": "2.1", "error": {"code": -32600, "message": "Invalid Request"}, "id": null}"#; // invalid version
@@ -121,6 +1098,700 @@ mod tests {
         let req = r#"{"jsonrpc": "2.0", "error": {"code": -32600, "message": "Invalid Request"}}"#; // no id
         let req_obj = TryInto::<Response>::try_into(req);
         assert!(req_obj.is_err());
+
+        let req = r#"{"jsonrpc": "2.0", "result": 1, "error": {"code": -32600, "message": "Invalid Request"}, "id": 1}"#; // result and error
+        let req_obj = TryInto::<Response>::try_into(req);
+        assert!(req_obj.is_err());
+
+        let req = r#"{"jsonrpc": "2.0", "error": {"code": -32600, "message": "Invalid Request"}, "result": null, "id": 1}"#; // error and null result
+        let req_obj = TryInto::<Response>::try_into(req);
+        assert!(req_obj.is_err());
+
+        let req = r#"{"jsonrpc": "2.0", "result": 1, "error": null, "id": 1}"#; // null error
+        let req_obj = TryInto::<Response>::try_into(req);
+        assert!(req_obj.is_err());
+
+        let req = r#"{"jsonrpc": "2.0", "error": {"code": -1, "message": "x", "foo": 1}, "id": 1}"#; // unknown error field
+        let req_obj = TryInto::<Response>::try_into(req);
+        assert!(req_obj.is_err());
+        let status =
+            serde_json::from_str::<Status>(r#"{"error": {"code": -1, "message": "x", "foo": 1}}"#);
+        assert!(status.is_err());
+    }
+
+    #[test]
+    fn error_response_ids() {
+        let rsp = error_response(Some(Id::Number(4)), -32602, "Invalid params");
+        assert_eq!(
+            rsp,
+            Response::builder().id(4).error().invalid_params().build()
+        );
+
+        let rsp = error_response(None, -32600, "Invalid Request");
+        assert_eq!(rsp.id, Id::Null);
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::InvalidRequest));
+    }
+
+    #[test]
+    fn parse_failure_response() {
+        let err = TryInto::<crate::request::Request>::try_into(r#"{"jsonrpc":"2.0","method""#)
+            .unwrap_err();
+        let rsp = error_response_for_parse_failure(&err);
+        assert_eq!(rsp.id, Id::Null);
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::Parse));
+
+        let err = TryInto::<crate::request::Request>::try_into(
+            r#"{"jsonrpc":"1.0","method":"a","id":1}"#,
+        )
+        .unwrap_err();
+        let rsp = error_response_for_parse_failure(&err);
+        assert_eq!(rsp.id, Id::Null);
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::InvalidRequest));
+    }
+
+    #[test]
+    fn validate_null_id() {
+        let rsp = TryInto::<Response>::try_into(r#"{"jsonrpc":"2.0","result":1,"id":null}"#);
+        assert!(matches!(
+            rsp.unwrap().validate(),
+            Err(Error::InvalidType(_))
+        ));
+
+        let rsp = Response::builder().id(()).error().parse_error().build();
+        assert!(rsp.validate().is_ok());
+        let rsp = Response::builder().id(1).success().build();
+        assert!(rsp.validate().is_ok());
+    }
+
+    #[test]
+    fn ensure_id_matches() {
+        let rsp = Response::builder().id(1).success().build();
+        assert!(rsp.ensure_id_matches(&Id::Number(1)).is_ok());
+
+        let err = rsp.ensure_id_matches(&Id::String("1".to_string()));
+        assert!(matches!(
+            err,
+            Err(Error::IdMismatch {
+                expected: Id::String(_),
+                got: Id::Number(1)
+            })
+        ));
+        assert!(rsp.ensure_id_matches(&Id::Number(2)).is_err());
+        assert!(rsp.ensure_id_matches(&Id::Null).is_err());
+    }
+
+    #[test]
+    fn validate_against() {
+        let req = crate::request::Request::builder()
+            .id(1)
+            .method("get")
+            .build();
+        let rsp = Response::builder().id(&req).success().build();
+        assert!(rsp.validate_against(&req).is_ok());
+        let rsp = Response::builder()
+            .id(Id::Null)
+            .error()
+            .parse_error()
+            .build();
+        assert!(rsp.validate_against(&req).is_ok());
+
+        let rsp = Response::builder().id(2).success().build();
+        assert!(matches!(
+            rsp.validate_against(&req),
+            Err(Error::IdMismatch {
+                expected: Id::Number(1),
+                got: Id::Number(2)
+            })
+        ));
+
+        let req = crate::request::Request::builder()
+            .id(Id::Null)
+            .method("get")
+            .build();
+        let rsp = Response::builder().id(Id::Null).success().build();
+        assert!(matches!(
+            rsp.validate_against(&req),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn id_from_response() {
+        let rsp = Response::builder()
+            .id("abc")
+            .error()
+            .internal_error()
+            .build();
+        let retry = crate::request::Request::builder()
+            .id(&rsp)
+            .method("get")
+            .build();
+        assert_eq!(retry.id, rsp.id);
+        let aggregated = Response::builder().id(&rsp).success().build();
+        assert_eq!(aggregated.id, Id::String("abc".to_string()));
+    }
+
+    #[test]
+    fn serialize_error_data() {
+        let rsp = Response::builder().id(1).error().invalid_request().build();
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"Invalid Request"}}"#
+        );
+
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .invalid_request()
+            .data(serde_json::json!([1]))
+            .build();
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"Invalid Request","data":[1]}}"#
+        );
+    }
+
+    #[test]
+    fn status_from_tuple() {
+        let status = Status::from((-32000, "busy"));
+        let rsp = Response::new(1, status.clone());
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"busy"}}"#
+        );
+        let new_rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+        assert_eq!(new_rsp.status, status);
+
+        let status = Status::from((-32000, "busy", serde_json::json!({"retry": true})));
+        let rsp = Response::new(1, status.clone());
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"busy","data":{"retry":true}}}"#
+        );
+        let new_rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+        assert_eq!(new_rsp.status, status);
+    }
+
+    #[test]
+    fn matches_ignoring_data() {
+        let a = Response::builder()
+            .id(1)
+            .error()
+            .internal_error()
+            .data(serde_json::json!({"ts": 1}))
+            .build();
+        let b = Response::builder()
+            .id(1)
+            .error()
+            .internal_error()
+            .data(serde_json::json!({"ts": 2}))
+            .build();
+        assert_ne!(a, b);
+        assert!(a.matches_ignoring_data(&b));
+
+        let c = Response::builder().id(1).error().internal_error().build();
+        assert!(a.matches_ignoring_data(&c));
+
+        let d = Response::builder().id(2).error().internal_error().build();
+        assert!(!a.matches_ignoring_data(&d));
+
+        let e = Response::builder().id(1).error().invalid_params().build();
+        assert!(!a.matches_ignoring_data(&e));
+
+        let f = Response::builder().id(1).success().build();
+        assert!(!a.matches_ignoring_data(&f));
+        assert!(f.matches_ignoring_data(&f.clone()));
+    }
+
+    #[cfg(feature = "tagged-debug")]
+    #[test]
+    fn to_tagged_string() {
+        let rsp = Response::builder().id(1).success().result(5.into()).build();
+        assert_eq!(
+            rsp.to_tagged_string().unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"status":"success","result":5}"#
+        );
+
+        let rsp = Response::builder().id(1).error().invalid_request().build();
+        assert_eq!(
+            rsp.to_tagged_string().unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"status":"error","error":{"code":-32600,"message":"Invalid Request"}}"#
+        );
+        assert_eq!(
+            TryInto::<String>::try_into(rsp).unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32600,"message":"Invalid Request"}}"#
+        );
+    }
+
+    #[test]
+    fn map_result() {
+        let rsp = Response::builder().id(1).success().result(5.into()).build();
+        let rsp = rsp.map_result(|v| serde_json::json!({"wrapped": v}));
+        assert_eq!(rsp.id, Id::Number(1));
+        assert_eq!(
+            rsp.status,
+            Status::Success(serde_json::json!({"wrapped": 5}))
+        );
+
+        let rsp = Response::builder().id(1).error().internal_error().build();
+        assert_eq!(rsp.clone().map_result(|_| serde_json::Value::Null), rsp);
+    }
+
+    #[test]
+    fn status_accessors() {
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result(true.into())
+            .build();
+        assert_eq!(rsp.status(), &Status::Success(true.into()));
+        assert!(rsp.is_success());
+        assert!(!rsp.is_error());
+        assert_eq!(rsp.status_discriminant(), "success");
+
+        let rsp = Response::builder().id(1).error().internal_error().build();
+        assert!(matches!(rsp.status(), Status::Error { code: -32603, .. }));
+        assert!(!rsp.is_success());
+        assert!(rsp.is_error());
+        assert_eq!(rsp.status_discriminant(), "error");
+    }
+
+    #[test]
+    fn error_kind() {
+        let rsp = Response::builder().id(1).error().method_not_found().build();
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::MethodNotFound));
+
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .code(-1)
+            .message("x")
+            .build();
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::Application));
+
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .code(-32050)
+            .message("Server error")
+            .build();
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::Server));
+
+        assert_eq!(ErrorKind::from_code(-32700), ErrorKind::Parse);
+        assert_eq!(ErrorKind::from_code(-32600), ErrorKind::InvalidRequest);
+        assert_eq!(ErrorKind::from_code(-32602), ErrorKind::InvalidParams);
+        assert_eq!(ErrorKind::from_code(-32603), ErrorKind::Internal);
+        assert_eq!(ErrorKind::from_code(-32100), ErrorKind::Application);
+
+        let rsp = Response::builder().id(1).success().build();
+        assert_eq!(rsp.error_kind(), None);
+
+        let mut kinds: Vec<_> = [-1, -32000, -32601, -32700, -32603]
+            .into_iter()
+            .map(ErrorKind::from_code)
+            .collect();
+        kinds.sort();
+        assert_eq!(
+            kinds,
+            [
+                ErrorKind::Parse,
+                ErrorKind::MethodNotFound,
+                ErrorKind::Internal,
+                ErrorKind::Server,
+                ErrorKind::Application
+            ]
+        );
+    }
+
+    #[test]
+    fn response_error() {
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .method_not_found()
+            .data(serde_json::json!("subtract"))
+            .build();
+        let err = rsp.clone().into_error().unwrap_err();
+        assert_eq!(err.code, -32601);
+        assert_eq!(err.message, "Method not found");
+        assert_eq!(err.data, Some(serde_json::json!("subtract")));
+        assert_eq!(err.to_string(), "JSON-RPC error -32601: Method not found");
+        assert_eq!(TryInto::<ResponseError>::try_into(rsp).unwrap(), err);
+
+        let rsp = Response::builder().id(1).success().build();
+        assert_eq!(rsp.clone().into_error(), Ok(rsp.clone()));
+        assert!(TryInto::<ResponseError>::try_into(rsp).is_err());
+    }
+
+    #[test]
+    fn from_response_error() {
+        let upstream = Response::builder()
+            .id(1)
+            .error()
+            .code(-32000)
+            .message("busy")
+            .data(serde_json::json!({"retry": true}))
+            .build();
+        let err = upstream.clone().into_error().unwrap_err();
+        let rsp = Response::from_response_error("downstream-7", &err);
+        assert_eq!(rsp.id, Id::String("downstream-7".to_string()));
+        assert_eq!(rsp.status, upstream.status);
+        assert_eq!(rsp.into_error().unwrap_err(), err);
+    }
+
+    #[test]
+    fn from_handler_result() {
+        let rsp = Response::try_from((Id::Number(1), Ok::<_, ResponseError>(vec![1, 2]))).unwrap();
+        let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+        assert_eq!(rsp_str, r#"{"jsonrpc":"2.0","id":1,"result":[1,2]}"#);
+        assert_eq!(
+            TryInto::<Response>::try_into(rsp_str.as_str()).unwrap(),
+            rsp
+        );
+
+        let err = ResponseError {
+            code: -32000,
+            message: "busy".to_string(),
+            data: Some(serde_json::json!({"retry": true})),
+        };
+        let rsp = Response::try_from((Id::from("a"), Err::<(), _>(err.clone()))).unwrap();
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        let rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+        assert_eq!(rsp.id, Id::from("a"));
+        assert_eq!(rsp.into_error(), Err(err));
+
+        let mut map = std::collections::HashMap::new();
+        map.insert(vec![1], 1);
+        let rsp = Response::try_from((Id::Number(1), Ok::<_, ResponseError>(map)));
+        assert!(matches!(rsp, Err(Error::Serde(_))));
+    }
+
+    #[test]
+    fn write_to() {
+        let obj = Response::builder().id(10).error().invalid_request().build();
+        let mut buf = Vec::new();
+        obj.write_to(&mut buf).unwrap();
+        #[cfg(feature = "bytes")]
+        {
+            let mut bytes = bytes::BytesMut::new();
+            obj.encode_into(&mut bytes).unwrap();
+            assert_eq!(bytes, buf);
+        }
+        assert_eq!(buf, TryInto::<String>::try_into(obj).unwrap().into_bytes());
+    }
+
+    #[test]
+    fn predefined_errors_with_data() {
+        type Predefined =
+            fn(
+                builder::ErrorBuilder<builder::IdNone, builder::CodeNone, builder::MessageNone>,
+            )
+                -> builder::ErrorBuilder<builder::IdNone, builder::Code, builder::Message>;
+        let predefined: [(Predefined, i32); 6] = [
+            (|b| b.parse_error(), -32700),
+            (|b| b.invalid_request(), -32600),
+            (|b| b.method_not_found(), -32601),
+            (|b| b.invalid_params(), -32602),
+            (|b| b.internal_error(), -32603),
+            (|b| b.server_error(-32050), -32050),
+        ];
+        let data = serde_json::json!({"detail": "sum"});
+        for (error, code) in predefined {
+            let after = error(Response::builder().error())
+                .data(data.clone())
+                .id(1)
+                .build();
+            let before = error(Response::builder().error().data(data.clone()))
+                .id(1)
+                .build();
+            assert_eq!(after, before);
+            let err = after.into_error().unwrap_err();
+            assert_eq!(err.code, code);
+            assert_eq!(err.data, Some(data.clone()));
+        }
+    }
+
+    #[test]
+    fn server_error_code_range() {
+        assert_eq!(i32::from(builder::ServerErrorCode::new(-32000)), -32000);
+        assert_eq!(i32::from(builder::ServerErrorCode::new(-32050)), -32050);
+        assert_eq!(i32::from(builder::ServerErrorCode::new(-32099)), -32099);
+    }
+
+    #[test]
+    #[should_panic(expected = "server error code range")]
+    fn server_error_code_above_range() {
+        builder::ServerErrorCode::new(-31999);
+    }
+
+    #[test]
+    #[should_panic(expected = "server error code range")]
+    fn server_error_code_below_range() {
+        builder::ServerErrorCode::new(-32100);
+    }
+
+    #[test]
+    fn data_from_error() {
+        let err = Error::from(serde_json::from_str::<i32>("x").unwrap_err());
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .internal_error()
+            .data_from_error_chain(&err)
+            .build();
+        let data = rsp.into_error().unwrap_err().data.unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!([
+                "serde error: expected value at line 1 column 1",
+                "expected value at line 1 column 1"
+            ])
+        );
+
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .internal_error()
+            .data_from_error(&err)
+            .build();
+        assert_eq!(
+            rsp.into_error().unwrap_err().data,
+            Some(serde_json::json!([
+                "serde error: expected value at line 1 column 1"
+            ]))
+        );
+    }
+
+    #[test]
+    fn builder_message_static() {
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .code(-32000)
+            .message_static("busy")
+            .build();
+        assert!(matches!(
+            &rsp.status,
+            Status::Error {
+                message: Cow::Borrowed("busy"),
+                ..
+            }
+        ));
+        assert_eq!(
+            TryInto::<String>::try_into(rsp).unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32000,"message":"busy"}}"#
+        );
+
+        let rsp = Response::builder().id(1).error().internal_error().build();
+        assert!(matches!(
+            &rsp.status,
+            Status::Error {
+                message: Cow::Borrowed("Internal error"),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn new_error_from_code() {
+        let rsp = Response::new_error_from_code(1, -32601, "Method not found", None).unwrap();
+        assert_eq!(
+            rsp,
+            Response::builder().id(1).error().method_not_found().build()
+        );
+        let rsp = Response::new_error_from_code(1, -32601, "no such user", None).unwrap();
+        assert_eq!(rsp.error_kind(), Some(ErrorKind::MethodNotFound));
+        assert!(Response::new_error_from_code(1, -32050, "busy", None).is_ok());
+        assert!(Response::new_error_from_code(1, -1, "failed", None).is_ok());
+
+        for code in [-32768, -32500, -32604] {
+            assert!(matches!(
+                Response::new_error_from_code(1, code, "failed", None),
+                Err(Error::InvalidType(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn builder_error_with_message() {
+        let expected = Status::Error {
+            code: -32602,
+            message: "Invalid params: expected 2 params".into(),
+            data: Some(serde_json::json!(1)),
+        };
+        let rsp = Response::builder()
+            .id(1)
+            .error()
+            .invalid_params()
+            .with_message("Invalid params: expected 2 params")
+            .data(serde_json::json!(1))
+            .build();
+        assert_eq!(rsp.status, expected);
+
+        let rsp = Response::builder()
+            .error()
+            .data(serde_json::json!(1))
+            .invalid_params()
+            .with_message("Invalid params: expected 2 params")
+            .id(1)
+            .build();
+        assert_eq!(rsp.status, expected);
+    }
+
+    #[test]
+    fn builder_error_like() {
+        let original = Response::builder()
+            .id("abc")
+            .error()
+            .server_error(-32001)
+            .data(serde_json::json!("upstream"))
+            .build();
+        let rsp = Response::builder()
+            .error()
+            .like(&original)
+            .unwrap()
+            .message("retries exhausted")
+            .build();
+        assert_eq!(rsp.id, Id::String("abc".to_string()));
+        assert_eq!(
+            rsp.status,
+            Status::Error {
+                code: -32001,
+                message: "retries exhausted".into(),
+                data: None
+            }
+        );
+
+        let success = Response::builder()
+            .id(1)
+            .success()
+            .result(true.into())
+            .build();
+        assert!(matches!(
+            Response::builder().error().like(&success),
+            Err(Error::InvalidType(_))
+        ));
+    }
+
+    #[test]
+    fn builder_error_with() {
+        let data = Some(serde_json::json!({"retry": true}));
+        let rsp = Response::builder()
+            .error()
+            .with(-32000, "busy", data.clone())
+            .id(10)
+            .build();
+        assert_eq!(
+            rsp.status,
+            Status::Error {
+                code: -32000,
+                message: "busy".into(),
+                data
+            }
+        );
+
+        let rsp = Response::builder()
+            .id(10)
+            .error()
+            .with(-1, "failed", None)
+            .build();
+        assert!(matches!(
+            rsp.status,
+            Status::Error {
+                code: -1,
+                data: None,
+                ..
+            }
+        ));
+    }
+
+    #[cfg(feature = "base64")]
+    #[test]
+    fn result_bytes() {
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result_bytes(b"\x00\xffbinary")
+            .build();
+        assert_eq!(
+            rsp.status,
+            Status::Success(serde_json::json!("AP9iaW5hcnk="))
+        );
+        assert_eq!(rsp.result_bytes().unwrap(), b"\x00\xffbinary");
+
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result_str("!!")
+            .unwrap()
+            .build();
+        assert!(rsp.result_bytes().is_err());
+
+        let rsp = Response::builder().id(1).error().internal_error().build();
+        assert!(rsp.result_bytes().is_err());
+    }
+
+    #[cfg(feature = "raw")]
+    #[test]
+    fn result_raw() {
+        let raw = r#"{"n": 1.000000000000000001, "big": 123456789012345678901234567890}"#;
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result_raw(serde_json::value::RawValue::from_string(raw.to_string()).unwrap())
+            .build();
+        let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+        assert_eq!(
+            rsp_str,
+            format!(r#"{{"jsonrpc":"2.0","id":1,"result":{raw}}}"#)
+        );
+        assert_eq!(rsp.clone().into_error(), Ok(rsp.clone()));
+        assert!(rsp.matches_ignoring_data(&rsp.clone()));
+
+        let new_rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+        assert_eq!(new_rsp, rsp);
+
+        // Replacing the result drops the raw text.
+        let mut rsp = rsp;
+        rsp.status = Status::Success(1.into());
+        assert_eq!(
+            TryInto::<String>::try_into(rsp).unwrap(),
+            r#"{"jsonrpc":"2.0","id":1,"result":1}"#
+        );
+    }
+
+    #[test]
+    fn result_128() {
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result_u128(u128::MAX)
+            .build();
+        let rsp_str = TryInto::<String>::try_into(rsp).unwrap();
+        assert_eq!(
+            rsp_str,
+            r#"{"jsonrpc":"2.0","id":1,"result":"340282366920938463463374607431768211455"}"#
+        );
+        let rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+        assert_eq!(rsp.result_as_u128().unwrap(), u128::MAX);
+        assert!(rsp.result_as_i128().is_err());
+
+        let rsp = Response::builder()
+            .id(1)
+            .success()
+            .result_i128(i128::MIN)
+            .build();
+        assert_eq!(rsp.result_as_i128().unwrap(), i128::MIN);
+        assert!(rsp.result_as_u128().is_err());
+
+        let rsp = Response::builder().id(1).success().result(5.into()).build();
+        assert!(matches!(rsp.result_as_u128(), Err(Error::InvalidType(_))));
+        let rsp = Response::builder().id(1).error().internal_error().build();
+        assert!(matches!(rsp.result_as_i128(), Err(Error::InvalidType(_))));
     }
 
     #[test]
@@ -141,4 +1812,33 @@ mod tests {
         let new_req = TryInto::<Response>::try_into(rsp_str.as_str());
         assert!(new_req.is_ok());
     }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn arbitrary_round_trip() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let bytes: Vec<u8> = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 13) as u8)
+            .collect();
+        let mut u = Unstructured::new(&bytes);
+        while !u.is_empty() {
+            let obj = Response::arbitrary(&mut u).unwrap();
+            let obj_str = TryInto::<String>::try_into(obj.clone()).unwrap();
+            assert_eq!(
+                TryInto::<Response>::try_into(obj_str.as_str()).unwrap(),
+                obj
+            );
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn proptest_round_trip(rsp in arb_response()) {
+            let rsp_str = TryInto::<String>::try_into(rsp.clone()).unwrap();
+            let new_rsp = TryInto::<Response>::try_into(rsp_str.as_str()).unwrap();
+            proptest::prop_assert_eq!(new_rsp, rsp);
+        }
+    }
 }
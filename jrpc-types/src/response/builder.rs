@@ -1,11 +1,17 @@
 //! This module implements a Builder class for the Request object.
 
+use std::borrow::Cow;
+
 use crate::{
     error::Error,
     id::Id as JId,
     response::{Response, Status},
+    version::Version,
 };
 
+#[cfg(feature = "raw")]
+use crate::response::RawResult;
+
 // =======================
 // Type State Structs
 // =======================
@@ -14,10 +20,21 @@ pub struct Id(JId);
 pub struct CodeNone;
 pub struct Code(i32);
 pub struct MessageNone;
-pub struct Message(String);
+pub struct Message(Cow<'static, str>);
 // =======================
 
 /// The Builder class for a Request object.
+///
+/// `success()` and `error()` consume the builder, so once a branch is picked the response is
+/// committed to it:
+/// ```compile_fail
+/// use jrpc_types::JsonRpcResponse;
+///
+/// let builder = JsonRpcResponse::builder().id(1);
+/// let success = builder.success();
+/// let error = builder.error(); // `builder` was moved by `success()`
+/// ```
+#[must_use = "builders do nothing unless `build()` is called"]
 pub struct Builder<I> {
     id: I,
 }
@@ -42,7 +59,9 @@ impl<I> Builder<I> {
     pub fn success(self) -> SuccessBuilder<I> {
         SuccessBuilder {
             id: self.id,
-            result: None,
+            status: Status::Success(serde_json::Value::Null),
+            #[cfg(feature = "raw")]
+            raw_result: None,
         }
     }
 
@@ -56,16 +75,21 @@ impl<I> Builder<I> {
     }
 }
 
+#[must_use = "builders do nothing unless `build()` is called"]
 pub struct SuccessBuilder<I> {
     id: I,
-    result: Option<serde_json::Value>,
+    status: Status,
+    #[cfg(feature = "raw")]
+    raw_result: Option<RawResult>,
 }
 
 impl SuccessBuilder<IdNone> {
     pub fn id<T: Into<JId>>(self, i: T) -> SuccessBuilder<Id> {
         SuccessBuilder {
             id: Id(i.into()),
-            result: self.result,
+            status: self.status,
+            #[cfg(feature = "raw")]
+            raw_result: self.raw_result,
         }
     }
 }
@@ -74,37 +98,93 @@ impl<I> SuccessBuilder<I> {
     pub fn result(self, p: serde_json::Value) -> SuccessBuilder<I> {
         SuccessBuilder {
             id: self.id,
-            result: Some(p),
+            status: Status::Success(p),
+            #[cfg(feature = "raw")]
+            raw_result: None,
         }
     }
 
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn result_serialize<T: serde::Serialize>(self, p: T) -> Result<SuccessBuilder<I>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         Ok(SuccessBuilder {
             id: self.id,
-            result: Some(value),
+            status: Status::Success(value),
+            #[cfg(feature = "raw")]
+            raw_result: None,
         })
     }
 
+    /// Sets a binary result, encoded as a standard base64 string.
+    ///
+    /// This is a common convention, not part of the JSON-RPC spec, so both peers need to agree on it.
+    #[cfg(feature = "base64")]
+    pub fn result_bytes(self, bytes: &[u8]) -> SuccessBuilder<I> {
+        use base64::Engine;
+
+        self.result(
+            base64::engine::general_purpose::STANDARD
+                .encode(bytes)
+                .into(),
+        )
+    }
+
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn result_str(self, p: &str) -> Result<SuccessBuilder<I>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         Ok(SuccessBuilder {
             id: self.id,
-            result: Some(value),
+            status: Status::Success(value),
+            #[cfg(feature = "raw")]
+            raw_result: None,
         })
     }
+
+    /// Sets a 128-bit integer result, encoded as a decimal string.
+    ///
+    /// JSON numbers can't hold 128-bit integers precisely, so this uses the common string
+    /// encoding convention. Read it back with `Response::result_as_u128`.
+    pub fn result_u128(self, v: u128) -> SuccessBuilder<I> {
+        self.result(v.to_string().into())
+    }
+
+    /// Sets a 128-bit integer result, encoded as a decimal string.
+    ///
+    /// JSON numbers can't hold 128-bit integers precisely, so this uses the common string
+    /// encoding convention. Read it back with `Response::result_as_i128`.
+    pub fn result_i128(self, v: i128) -> SuccessBuilder<I> {
+        self.result(v.to_string().into())
+    }
+
+    /// Sets a result that was already serialized, e.g. by an upstream server when proxying.
+    ///
+    /// The raw JSON is emitted verbatim, so large numbers and formatting are preserved exactly.
+    #[cfg(feature = "raw")]
+    pub fn result_raw(self, raw: Box<serde_json::value::RawValue>) -> SuccessBuilder<I> {
+        let raw_result = RawResult::new(raw);
+        SuccessBuilder {
+            id: self.id,
+            status: Status::Success(raw_result.value.clone()),
+            raw_result: Some(raw_result),
+        }
+    }
 }
 
 impl SuccessBuilder<Id> {
     pub fn build(self) -> Response {
         Response {
-            jsonrpc: "2.0".to_string(),
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
             id: self.id.0,
-            status: Status::Success(self.result.unwrap_or(serde_json::Value::Null)),
+            status: self.status,
+            #[cfg(feature = "raw")]
+            raw_result: self.raw_result,
         }
     }
 }
 
+#[must_use = "builders do nothing unless `build()` is called"]
 pub struct ErrorBuilder<I, C, M> {
     id: I,
     code: C,
@@ -123,6 +203,27 @@ impl<C, M> ErrorBuilder<IdNone, C, M> {
     }
 }
 
+impl ErrorBuilder<IdNone, CodeNone, MessageNone> {
+    /// Copies the id and code of the error response `other`, leaving only the message to set,
+    /// e.g. when fabricating a fallback error that must correlate with the original request.
+    ///
+    /// Fails if `other` is a success response.
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
+    pub fn like(self, other: &Response) -> Result<ErrorBuilder<Id, Code, MessageNone>, Error> {
+        match other.status {
+            Status::Error { code, .. } => Ok(ErrorBuilder {
+                id: Id(other.id.clone()),
+                code: Code(code),
+                message: self.message,
+                data: self.data,
+            }),
+            _ => Err(Error::InvalidType(
+                "cannot copy the error of a success Response".to_string(),
+            )),
+        }
+    }
+}
+
 impl<I, M> ErrorBuilder<I, CodeNone, M> {
     pub fn code(self, c: i32) -> ErrorBuilder<I, Code, M> {
         ErrorBuilder {
@@ -139,18 +240,56 @@ impl<I, C> ErrorBuilder<I, C, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: self.code,
-            message: Message(m.to_string()),
+            message: Message(m.to_string().into()),
+            data: self.data,
+        }
+    }
+
+    /// Like `message`, but borrows a static message instead of allocating a copy of it.
+    pub fn message_static(self, m: &'static str) -> ErrorBuilder<I, C, Message> {
+        ErrorBuilder {
+            id: self.id,
+            code: self.code,
+            message: Message(Cow::Borrowed(m)),
+            data: self.data,
+        }
+    }
+}
+
+impl<I, C> ErrorBuilder<I, C, Message> {
+    /// Replaces the message that's already set, e.g. to give a predefined error a more specific
+    /// message while keeping its code: `.invalid_params().with_message("expected 2 params")`.
+    pub fn with_message(self, m: &str) -> ErrorBuilder<I, C, Message> {
+        ErrorBuilder {
+            id: self.id,
+            code: self.code,
+            message: Message(m.to_string().into()),
             data: self.data,
         }
     }
 }
 
 impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
+    /// Sets the code, message, and data at once, e.g. when forwarding an upstream error.
+    pub fn with(
+        self,
+        code: i32,
+        message: &str,
+        data: Option<serde_json::Value>,
+    ) -> ErrorBuilder<I, Code, Message> {
+        ErrorBuilder {
+            id: self.id,
+            code: Code(code),
+            message: Message(message.to_string().into()),
+            data,
+        }
+    }
+
     pub fn parse_error(self) -> ErrorBuilder<I, Code, Message> {
         ErrorBuilder {
             id: self.id,
             code: Code(-32700),
-            message: Message("Parse error".to_string()),
+            message: Message(Cow::Borrowed("Parse error")),
             data: self.data,
         }
     }
@@ -159,7 +298,7 @@ impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: Code(-32600),
-            message: Message("Invalid Request".to_string()),
+            message: Message(Cow::Borrowed("Invalid Request")),
             data: self.data,
         }
     }
@@ -168,7 +307,7 @@ impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: Code(-32601),
-            message: Message("Method not found".to_string()),
+            message: Message(Cow::Borrowed("Method not found")),
             data: self.data,
         }
     }
@@ -177,7 +316,7 @@ impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: Code(-32602),
-            message: Message("Invalid params".to_string()),
+            message: Message(Cow::Borrowed("Invalid params")),
             data: self.data,
         }
     }
@@ -186,7 +325,7 @@ impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: Code(-32603),
-            message: Message("Internal error".to_string()),
+            message: Message(Cow::Borrowed("Internal error")),
             data: self.data,
         }
     }
@@ -195,13 +334,15 @@ impl<I> ErrorBuilder<I, CodeNone, MessageNone> {
         ErrorBuilder {
             id: self.id,
             code: Code(Into::<ServerErrorCode>::into(code).into()),
-            message: Message("Server error".to_string()),
+            message: Message(Cow::Borrowed("Server error")),
             data: self.data,
         }
     }
 }
 
 impl<I, C, M> ErrorBuilder<I, C, M> {
+    /// Sets the error data, which can be done anywhere in the chain, before or after the code
+    /// and message, e.g. `.error().method_not_found().data(json!("sum")).id(1).build()`.
     pub fn data(self, p: serde_json::Value) -> ErrorBuilder<I, C, M> {
         ErrorBuilder {
             id: self.id,
@@ -211,6 +352,7 @@ impl<I, C, M> ErrorBuilder<I, C, M> {
         }
     }
 
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn data_serialize<T: serde::Serialize>(self, p: T) -> Result<ErrorBuilder<I, C, M>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         Ok(ErrorBuilder {
@@ -221,6 +363,24 @@ impl<I, C, M> ErrorBuilder<I, C, M> {
         })
     }
 
+    /// Sets the data to the message of `err` only, as a JSON array of one message.
+    ///
+    /// Use [`ErrorBuilder::data_from_error_chain`] for the full cause chain, e.g. in debug
+    /// builds. This variant doesn't leak the internal details of the causes to clients.
+    pub fn data_from_error(self, err: &dyn std::error::Error) -> ErrorBuilder<I, C, M> {
+        self.data(serde_json::Value::Array(vec![err.to_string().into()]))
+    }
+
+    /// Sets the data to the messages of `err` and its `source()` chain, outermost first, as a
+    /// JSON array.
+    pub fn data_from_error_chain(self, err: &dyn std::error::Error) -> ErrorBuilder<I, C, M> {
+        let chain = std::iter::successors(Some(err), |e| e.source())
+            .map(|e| serde_json::Value::String(e.to_string()))
+            .collect();
+        self.data(serde_json::Value::Array(chain))
+    }
+
+    #[must_use = "the builder is inside the `Result`, use `?` or `unwrap` to keep building"]
     pub fn data_str(self, p: &str) -> Result<ErrorBuilder<I, C, M>, Error> {
         let value = serde_json::to_value(p).map_err(Error::from)?;
         Ok(ErrorBuilder {
@@ -235,13 +395,17 @@ impl<I, C, M> ErrorBuilder<I, C, M> {
 impl ErrorBuilder<Id, Code, Message> {
     pub fn build(self) -> Response {
         Response {
-            jsonrpc: "2.0".to_string(),
+            jsonrpc: Version::V2_0,
+            #[cfg(feature = "meta")]
+            meta: None,
             id: self.id.0,
             status: Status::Error {
                 code: self.code.0,
                 message: self.message.0,
                 data: self.data,
             },
+            #[cfg(feature = "raw")]
+            raw_result: None,
         }
     }
 }
@@ -251,8 +415,9 @@ impl ErrorBuilder<Id, Code, Message> {
 pub struct ServerErrorCode(i32);
 
 impl ServerErrorCode {
+    /// Panics if `val` is outside the server error range, -32099 to -32000 inclusive.
     pub const fn new(val: i32) -> Self {
-        if val <= -32000 || val >= -32099 {
+        if val > -32000 || val < -32099 {
             panic!("server error code range is -32099 <--> -32000");
         }
         Self(val)
@@ -0,0 +1,62 @@
+//! This module implements async adapters for line-delimited (NDJSON) transports.
+
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+use tokio_stream::{Stream, StreamExt, wrappers::LinesStream};
+
+use crate::{error::Error, response::Response};
+
+/// Reads one Response per line from `r`, skipping blank lines.
+///
+/// A line that fails to parse yields an error, but the stream carries on with the next line.
+/// The stream ends once `r` reaches EOF.
+pub fn response_stream<R: AsyncBufRead>(r: R) -> impl Stream<Item = Result<Response, Error>> {
+    LinesStream::new(r.lines()).filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(Response::try_from(line.as_str())),
+        Err(e) => Some(Err(e.into())),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::id::Id;
+
+    #[tokio::test]
+    async fn response_stream_lines() {
+        let data = concat!(
+            r#"{"jsonrpc":"2.0","result":1,"id":1}"#,
+            "\n\n",
+            r#"{"jsonrpc":"2.0","result":1}"#,
+            "\r\n",
+            r#"{"jsonrpc":"2.0","error":{"code":-32601,"message":"Method not found"},"id":2}"#,
+        );
+        let stream = response_stream(data.as_bytes());
+        tokio::pin!(stream);
+
+        assert_eq!(stream.next().await.unwrap().unwrap().id, Id::Number(1));
+        assert!(matches!(stream.next().await, Some(Err(Error::Serde(_)))));
+        assert_eq!(stream.next().await.unwrap().unwrap().id, Id::Number(2));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn response_stream_partial_lines() {
+        let (client, mut server) = tokio::io::duplex(8);
+        let writer = tokio::spawn(async move {
+            use tokio::io::AsyncWriteExt;
+
+            let data = b"{\"jsonrpc\":\"2.0\",\"result\":[1,2,3],\"id\":\"abc\"}\n";
+            for chunk in data.chunks(5) {
+                server.write_all(chunk).await.unwrap();
+            }
+        });
+        let stream = response_stream(tokio::io::BufReader::new(client));
+        tokio::pin!(stream);
+
+        let rsp = stream.next().await.unwrap().unwrap();
+        assert_eq!(rsp.id, Id::String("abc".to_string()));
+        writer.await.unwrap();
+        assert!(stream.next().await.is_none());
+    }
+}
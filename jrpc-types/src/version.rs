@@ -1,28 +1,200 @@
 //! This module implements the validation logic for JSON-RPC "jsonrpc" field.
 
-use serde::{Deserializer, de::Visitor};
+use std::borrow::Cow;
 
+use serde::{Deserializer, Serializer, de::Visitor};
+
+use crate::options::ParseOptions;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The "jsonrpc" field of JSON-RPC objects.
+///
+/// The only valid version is "2.0", unless parsing with
+/// [`ParseOptions::forward_compatible`], in which case the observed "2.x" version is kept.
+pub struct Version {
+    raw: Cow<'static, str>,
+}
+
+impl Version {
+    /// The "2.0" version of the spec.
+    pub const V2_0: Version = Version {
+        raw: Cow::Borrowed("2.0"),
+    };
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl Default for Version {
+    fn default() -> Self {
+        Version::V2_0
+    }
+}
+
+/// Deserializes a "jsonrpc" field into a `String`, for structs that still store it as one.
+#[deprecated(note = "use the `Version` type for the \"jsonrpc\" field")]
 pub fn version_deserialize<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>,
 {
-    struct VersionVisitor;
-    impl<'de> Visitor<'de> for VersionVisitor {
-        type Value = String;
+    <Version as serde::Deserialize>::deserialize(deserializer).map(|v| v.as_str().to_string())
+}
 
-        fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-            formatter.write_str(r#"jsonrpc version MUST be "2.0""#)
-        }
+/// Whether `v` is an accepted "jsonrpc" version with `options`, see
+/// [`ParseOptions::forward_compatible`].
+pub fn is_compatible(v: &str, options: &ParseOptions) -> bool {
+    parse(v, options.forward_compatible).is_some()
+}
+
+fn parse(v: &str, forward_compatible: bool) -> Option<Version> {
+    if v == "2.0" {
+        return Some(Version::V2_0);
+    }
+    let minor = v.strip_prefix("2.")?;
+    if !forward_compatible || minor.is_empty() || !minor.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some(Version {
+        raw: Cow::Owned(v.to_string()),
+    })
+}
+
+impl PartialEq<str> for Version {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Version {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl serde::Serialize for Version {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Version {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VersionVisitor;
+        impl<'de> Visitor<'de> for VersionVisitor {
+            type Value = Version;
 
-        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-        where
-            E: serde::de::Error,
-        {
-            match v {
-                "2.0" => Ok(v.to_string()),
-                _ => Err(E::custom(format!("jsonrpc version NOT 2.0: {v}"))),
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str(r#"jsonrpc version MUST be "2.0""#)
             }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                parse(v, crate::options::current().forward_compatible)
+                    .ok_or_else(|| E::custom(format!("jsonrpc version NOT 2.0: {v}")))
+            }
+        }
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Version {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Version".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "const": "2.0"
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, serde::Serialize)]
+    struct TestObject {
+        pub jsonrpc: Version,
+    }
+
+    #[test]
+    fn version() {
+        assert_eq!(Version::V2_0.as_str(), "2.0");
+        assert_eq!(Version::default(), Version::V2_0);
+
+        let test_obj_str = r#"{"jsonrpc":"2.0"}"#;
+        let to = serde_json::from_str::<TestObject>(test_obj_str);
+        assert!(to.is_ok(), "{:?}", to.unwrap_err().to_string());
+        let to_str = serde_json::to_string(&to.unwrap());
+        assert_eq!(to_str.unwrap(), test_obj_str);
+    }
+
+    #[test]
+    fn forward_compatible() {
+        let options = ParseOptions {
+            forward_compatible: true,
+            ..ParseOptions::default()
+        };
+        assert!(is_compatible("2.0", &ParseOptions::default()));
+        assert!(!is_compatible("2.1", &ParseOptions::default()));
+        assert!(is_compatible("2.1", &options));
+
+        assert_eq!(parse("2.0", false), Some(Version::V2_0));
+        assert_eq!(parse("2.1", false), None);
+        let version = parse("2.1", true).unwrap();
+        assert_eq!(version.as_str(), "2.1");
+        let to = TestObject { jsonrpc: version };
+        assert_eq!(serde_json::to_string(&to).unwrap(), r#"{"jsonrpc":"2.1"}"#);
+        assert_eq!(parse("2.10", true).unwrap().as_str(), "2.10");
+        assert_eq!(parse("2.", true), None);
+        assert_eq!(parse("2.1a", true), None);
+        assert_eq!(parse("3.0", true), None);
+
+        let obj = r#"{"jsonrpc":"2.10"}"#;
+        assert!(serde_json::from_str::<TestObject>(obj).is_err());
+        let to = crate::options::with(&options, || serde_json::from_str::<TestObject>(obj));
+        assert_eq!(to.unwrap().jsonrpc, "2.10");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn deprecated_version_deserialize() {
+        #[derive(serde::Deserialize)]
+        struct LegacyObject {
+            #[serde(deserialize_with = "version_deserialize")]
+            jsonrpc: String,
         }
+
+        let to = serde_json::from_str::<LegacyObject>(r#"{"jsonrpc":"2.0"}"#);
+        assert_eq!(to.unwrap().jsonrpc, "2.0");
+        assert!(serde_json::from_str::<LegacyObject>(r#"{"jsonrpc":"1.0"}"#).is_err());
+    }
+
+    #[test]
+    fn negative_serde_tests() {
+        let obj = r#"{"jsonrpc":"1.0"}"#;
+        assert!(serde_json::from_str::<TestObject>(obj).is_err());
+
+        let obj = r#"{"jsonrpc":2.0}"#;
+        assert!(serde_json::from_str::<TestObject>(obj).is_err());
+
+        let obj = r#"{}"#;
+        assert!(serde_json::from_str::<TestObject>(obj).is_err());
     }
-    deserializer.deserialize_string(VersionVisitor)
 }